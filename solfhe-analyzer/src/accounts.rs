@@ -0,0 +1,598 @@
+// Her talimat için tipli hesap ayrıştırma katmanı.
+// Hesaplar sırayla okunur; eksik, imzasız, yazılamaz ya da yanlış sahipli bir
+// hesap, adıyla birlikte loglanıp kesin bir `SolfheError` koduyla reddedilir.
+
+use solana_program::{
+    account_info::AccountInfo,
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    entrypoint::ProgramResult,
+    instruction::Instruction,
+    msg,
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::error::SolfheError;
+use light_sdk::constants::{PROGRAM_ID_ACCOUNT_COMPRESSION, PROGRAM_ID_LIGHT_SYSTEM, PROGRAM_ID_NOOP};
+
+use crate::light::{
+    find_account_compression_authority_address, find_cpi_authority_address, find_registered_program_address,
+    CPI_AUTHORITY_SEED,
+};
+
+use crate::state::{
+    find_reward_authority_address, find_reward_mint_address, Campaign, ClaimRecord, ProgramConfig,
+};
+
+pub struct AccountParser<'a, 'b> {
+    accounts: &'a [AccountInfo<'b>],
+    index: usize,
+}
+
+impl<'a, 'b> AccountParser<'a, 'b> {
+    pub fn new(accounts: &'a [AccountInfo<'b>]) -> Self {
+        Self { accounts, index: 0 }
+    }
+
+    pub fn next(&mut self, name: &str) -> Result<&'a AccountInfo<'b>, ProgramError> {
+        let account = self.accounts.get(self.index).ok_or_else(|| {
+            msg!("Missing account #{} ({})", self.index, name);
+            SolfheError::MissingAccount
+        })?;
+        self.index += 1;
+        Ok(account)
+    }
+
+    pub fn next_signer(&mut self, name: &str) -> Result<&'a AccountInfo<'b>, ProgramError> {
+        let account = self.next(name)?;
+        expect_signer(account, name)?;
+        Ok(account)
+    }
+
+    pub fn next_writable(&mut self, name: &str) -> Result<&'a AccountInfo<'b>, ProgramError> {
+        let account = self.next(name)?;
+        expect_writable(account, name)?;
+        Ok(account)
+    }
+
+    pub fn next_signer_writable(&mut self, name: &str) -> Result<&'a AccountInfo<'b>, ProgramError> {
+        let account = self.next_signer(name)?;
+        expect_writable(account, name)?;
+        Ok(account)
+    }
+
+    pub fn next_owned(
+        &mut self,
+        name: &str,
+        owner: &Pubkey,
+        writable: bool,
+    ) -> Result<&'a AccountInfo<'b>, ProgramError> {
+        let account = self.next(name)?;
+        expect_owner(account, owner, name)?;
+        if writable {
+            expect_writable(account, name)?;
+        }
+        Ok(account)
+    }
+
+    pub fn next_program(&mut self, name: &str, program_id: &Pubkey) -> Result<&'a AccountInfo<'b>, ProgramError> {
+        let account = self.next(name)?;
+        if account.key != program_id || !account.executable {
+            msg!("Account {} must be the program {}", name, program_id);
+            return Err(SolfheError::IncorrectProgramAccount.into());
+        }
+        Ok(account)
+    }
+
+    pub fn remaining(self) -> &'a [AccountInfo<'b>] {
+        &self.accounts[self.index.min(self.accounts.len())..]
+    }
+}
+
+pub fn expect_signer(account: &AccountInfo, name: &str) -> Result<(), ProgramError> {
+    if !account.is_signer {
+        msg!("Account {} must sign the transaction", name);
+        return Err(SolfheError::AccountNotSigner.into());
+    }
+    Ok(())
+}
+
+pub fn expect_writable(account: &AccountInfo, name: &str) -> Result<(), ProgramError> {
+    if !account.is_writable {
+        msg!("Account {} must be writable", name);
+        return Err(SolfheError::AccountNotWritable.into());
+    }
+    Ok(())
+}
+
+pub fn expect_owner(account: &AccountInfo, owner: &Pubkey, name: &str) -> Result<(), ProgramError> {
+    if account.owner != owner {
+        msg!("Account {} must be owned by {}", name, owner);
+        return Err(SolfheError::IncorrectAccountOwner.into());
+    }
+    Ok(())
+}
+
+pub fn expect_address(account: &AccountInfo, expected: &Pubkey, name: &str) -> Result<(), ProgramError> {
+    if account.key != expected {
+        msg!("Account {} must be {}", name, expected);
+        return Err(SolfheError::InvalidAccountAddress.into());
+    }
+    Ok(())
+}
+
+/// `AnalyzeLinks`: sonucu memo olarak yazar.
+pub struct MemoAccounts<'a, 'b> {
+    pub signer: &'a AccountInfo<'b>,
+    pub memo_program: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> MemoAccounts<'a, 'b> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let mut parser = AccountParser::new(accounts);
+        Ok(Self {
+            signer: parser.next_signer("signer")?,
+            memo_program: parser.next_program("memo_program", &spl_memo::id())?,
+        })
+    }
+}
+
+/// Light system programı `invoke_cpi` çağrısının sabit hesapları, programın CPI
+/// yetkilisi, program yapılandırması ve kullanılacak durum ağacı ile nullifier kuyruğu.
+pub struct LightSystemAccounts<'a, 'b> {
+    pub light_program: &'a AccountInfo<'b>,
+    pub cpi_authority: &'a AccountInfo<'b>,
+    pub solfhe_program: &'a AccountInfo<'b>,
+    pub registered_program_pda: &'a AccountInfo<'b>,
+    pub noop_program: &'a AccountInfo<'b>,
+    pub account_compression_authority: &'a AccountInfo<'b>,
+    pub account_compression_program: &'a AccountInfo<'b>,
+    pub system_program: &'a AccountInfo<'b>,
+    pub config: &'a AccountInfo<'b>,
+    pub merkle_tree: &'a AccountInfo<'b>,
+    pub nullifier_queue: &'a AccountInfo<'b>,
+    pub cpi_authority_bump: u8,
+}
+
+impl<'a, 'b> LightSystemAccounts<'a, 'b> {
+    pub fn parse(program_id: &Pubkey, parser: &mut AccountParser<'a, 'b>) -> Result<Self, ProgramError> {
+        let light_program = parser.next_program("light_program", &PROGRAM_ID_LIGHT_SYSTEM)?;
+        let cpi_authority = parser.next("cpi_authority")?;
+        let solfhe_program = parser.next_program("solfhe_program", program_id)?;
+        let registered_program_pda = parser.next("registered_program_pda")?;
+        let noop_program = parser.next_program("noop_program", &PROGRAM_ID_NOOP)?;
+        let account_compression_authority = parser.next("account_compression_authority")?;
+        let account_compression_program =
+            parser.next_program("account_compression_program", &PROGRAM_ID_ACCOUNT_COMPRESSION)?;
+        let system_program = parser.next_program("system_program", &system_program::id())?;
+        let config = parser.next_owned("config", program_id, false)?;
+        let merkle_tree = parser.next_owned("merkle_tree", &PROGRAM_ID_ACCOUNT_COMPRESSION, true)?;
+        let nullifier_queue =
+            parser.next_owned("nullifier_queue", &PROGRAM_ID_ACCOUNT_COMPRESSION, true)?;
+
+        let (expected_config, _) = ProgramConfig::find_address(program_id);
+        expect_address(config, &expected_config, "config")?;
+        let (expected_authority, cpi_authority_bump) = find_cpi_authority_address(program_id);
+        expect_address(cpi_authority, &expected_authority, "cpi_authority")?;
+        let (expected_registered, _) = find_registered_program_address();
+        expect_address(registered_program_pda, &expected_registered, "registered_program_pda")?;
+        let (expected_cpi_authority, _) = find_account_compression_authority_address();
+        expect_address(
+            account_compression_authority,
+            &expected_cpi_authority,
+            "account_compression_authority",
+        )?;
+
+        Ok(Self {
+            light_program,
+            cpi_authority,
+            solfhe_program,
+            registered_program_pda,
+            noop_program,
+            account_compression_authority,
+            account_compression_program,
+            system_program,
+            config,
+            merkle_tree,
+            nullifier_queue,
+            cpi_authority_bump,
+        })
+    }
+
+    /// Ağaç ve kuyruk çifti yapılandırmada kayıtlı olmalıdır.
+    pub fn check_registered_tree(&self) -> Result<(), ProgramError> {
+        let config = ProgramConfig::unpack(&self.config.data.borrow())?;
+        if !config.is_registered(self.merkle_tree.key, self.nullifier_queue.key) {
+            return Err(SolfheError::UnregisteredStateTree.into());
+        }
+        Ok(())
+    }
+
+    /// `invoke_cpi` talimatını programın CPI yetkilisi adına imzalayarak Light'a iletir;
+    /// `remaining` ağaç ve kuyruklardan sonra eklenir.
+    pub fn invoke_signed(
+        &self,
+        instruction: &Instruction,
+        payer: &AccountInfo<'b>,
+        remaining: &[AccountInfo<'b>],
+    ) -> ProgramResult {
+        let mut account_infos = vec![
+            payer.clone(),
+            self.cpi_authority.clone(),
+            self.registered_program_pda.clone(),
+            self.noop_program.clone(),
+            self.account_compression_authority.clone(),
+            self.account_compression_program.clone(),
+            self.solfhe_program.clone(),
+            self.light_program.clone(),
+            self.system_program.clone(),
+            self.merkle_tree.clone(),
+            self.nullifier_queue.clone(),
+        ];
+        account_infos.extend(remaining.iter().cloned());
+        invoke_signed(
+            instruction,
+            &account_infos,
+            &[&[CPI_AUTHORITY_SEED, &[self.cpi_authority_bump]]],
+        )
+    }
+}
+
+/// `CompressAndTransfer`: hesap program sahiplidir, bu yüzden Light'a programın
+/// CPI yetkilisi imzalar; kalan hesaplar Light çağrısına aktarılır.
+pub struct CompressAndTransferAccounts<'a, 'b> {
+    pub payer: &'a AccountInfo<'b>,
+    pub light: LightSystemAccounts<'a, 'b>,
+    pub remaining: &'a [AccountInfo<'b>],
+}
+
+impl<'a, 'b> CompressAndTransferAccounts<'a, 'b> {
+    pub fn parse(program_id: &Pubkey, accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let mut parser = AccountParser::new(accounts);
+        let payer = parser.next_signer_writable("payer")?;
+        let light = LightSystemAccounts::parse(program_id, &mut parser)?;
+        Ok(Self {
+            payer,
+            light,
+            remaining: parser.remaining(),
+        })
+    }
+}
+
+/// `InitializeConfig`: yapılandırma hesabı henüz oluşturulmamıştır. Yönetici,
+/// programın yükseltme yetkilisi olmalıdır; `program_data` bunu kanıtlar.
+pub struct InitializeConfigAccounts<'a, 'b> {
+    pub admin: &'a AccountInfo<'b>,
+    pub config: &'a AccountInfo<'b>,
+    pub program_data: &'a AccountInfo<'b>,
+    pub system_program: &'a AccountInfo<'b>,
+    pub config_bump: u8,
+}
+
+impl<'a, 'b> InitializeConfigAccounts<'a, 'b> {
+    pub fn parse(program_id: &Pubkey, accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let mut parser = AccountParser::new(accounts);
+        let admin = parser.next_signer_writable("admin")?;
+        let config = parser.next_owned("config", &system_program::id(), true)?;
+        let program_data = parser.next_owned("program_data", &bpf_loader_upgradeable::id(), false)?;
+        let system_program = parser.next_program("system_program", &system_program::id())?;
+
+        let (expected_config, config_bump) = ProgramConfig::find_address(program_id);
+        expect_address(config, &expected_config, "config")?;
+        let (expected_program_data, _) =
+            Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+        expect_address(program_data, &expected_program_data, "program_data")?;
+
+        Ok(Self {
+            admin,
+            config,
+            program_data,
+            system_program,
+            config_bump,
+        })
+    }
+
+    /// `ProgramData` hesabındaki yükseltme yetkilisi; program değiştirilemez
+    /// yapılmışsa `None`. Bincode düzeni: u32 varyant (3), u64 slot,
+    /// `Option<Pubkey>` (1 bayt etiket + 32 bayt).
+    pub fn upgrade_authority(&self) -> Result<Option<Pubkey>, ProgramError> {
+        const PROGRAM_DATA_VARIANT: u32 = 3;
+        let data = self.program_data.data.borrow();
+        if data.len() < UpgradeableLoaderState::size_of_programdata_metadata() || data[..4] != PROGRAM_DATA_VARIANT.to_le_bytes() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        match data[12] {
+            0 => Ok(None),
+            1 => Ok(Some(Pubkey::new_from_array(
+                data[13..45].try_into().map_err(|_| ProgramError::InvalidAccountData)?,
+            ))),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+
+/// `RegisterStateTree`, `RemoveStateTree`.
+pub struct ConfigAdminAccounts<'a, 'b> {
+    pub admin: &'a AccountInfo<'b>,
+    pub config: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> ConfigAdminAccounts<'a, 'b> {
+    pub fn parse(program_id: &Pubkey, accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let mut parser = AccountParser::new(accounts);
+        let admin = parser.next_signer("admin")?;
+        let config = parser.next_owned("config", program_id, true)?;
+
+        let (expected_config, _) = ProgramConfig::find_address(program_id);
+        expect_address(config, &expected_config, "config")?;
+
+        Ok(Self { admin, config })
+    }
+}
+
+/// `RetrieveAndDecompress`: sıkıştırılmış hesap Light ile doğrulanır, ardından memo yazılır.
+pub struct RetrieveAndDecompressAccounts<'a, 'b> {
+    pub signer: &'a AccountInfo<'b>,
+    pub memo_program: &'a AccountInfo<'b>,
+    pub light: LightSystemAccounts<'a, 'b>,
+}
+
+impl<'a, 'b> RetrieveAndDecompressAccounts<'a, 'b> {
+    pub fn parse(program_id: &Pubkey, accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let mut parser = AccountParser::new(accounts);
+        Ok(Self {
+            signer: parser.next_signer_writable("signer")?,
+            memo_program: parser.next_program("memo_program", &spl_memo::id())?,
+            light: LightSystemAccounts::parse(program_id, &mut parser)?,
+        })
+    }
+}
+
+/// `ServeAd`: imzalayandan sonra gelen her hesap aday bir kampanyadır.
+pub struct ServeAdAccounts<'a, 'b> {
+    pub payer: &'a AccountInfo<'b>,
+    pub campaigns: &'a [AccountInfo<'b>],
+}
+
+impl<'a, 'b> ServeAdAccounts<'a, 'b> {
+    pub fn parse(program_id: &Pubkey, accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let mut parser = AccountParser::new(accounts);
+        let payer = parser.next_signer("payer")?;
+        let campaigns = parser.remaining();
+        for campaign in campaigns {
+            expect_owner(campaign, program_id, "campaign")?;
+        }
+        Ok(Self { payer, campaigns })
+    }
+}
+
+pub struct RewardUserAccounts<'a, 'b> {
+    pub user: &'a AccountInfo<'b>,
+    /// Raporu ortak imzalayan reklam veren ya da oracle.
+    pub attestor: &'a AccountInfo<'b>,
+    pub campaign: &'a AccountInfo<'b>,
+    pub vault: &'a AccountInfo<'b>,
+    pub user_token_account: &'a AccountInfo<'b>,
+    pub reward_mint: &'a AccountInfo<'b>,
+    pub reward_authority: &'a AccountInfo<'b>,
+    pub system_program: &'a AccountInfo<'b>,
+    pub token_program: &'a AccountInfo<'b>,
+    pub associated_token_program: &'a AccountInfo<'b>,
+    pub claim_record: &'a AccountInfo<'b>,
+    pub reward_authority_bump: u8,
+    pub claim_record_bump: u8,
+}
+
+impl<'a, 'b> RewardUserAccounts<'a, 'b> {
+    pub fn parse(program_id: &Pubkey, accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let mut parser = AccountParser::new(accounts);
+        let user = parser.next_signer_writable("user")?;
+        let attestor = parser.next_signer("attestor")?;
+        let campaign = parser.next_owned("campaign", program_id, true)?;
+        let vault = parser.next_owned("vault", program_id, true)?;
+        let user_token_account = parser.next_writable("user_token_account")?;
+        let reward_mint = parser.next_owned("reward_mint", &spl_token::id(), true)?;
+        let reward_authority = parser.next_owned("reward_authority", program_id, true)?;
+        let system_program = parser.next_program("system_program", &system_program::id())?;
+        let token_program = parser.next_program("token_program", &spl_token::id())?;
+        let associated_token_program =
+            parser.next_program("associated_token_program", &spl_associated_token_account::id())?;
+        // İlk ödülde henüz oluşturulmamış olabilir
+        let claim_record = parser.next_writable("claim_record")?;
+        if claim_record.owner != program_id {
+            expect_owner(claim_record, &system_program::id(), "claim_record")?;
+        }
+
+        let (expected_vault, _) = Campaign::find_vault_address(program_id, campaign.key);
+        expect_address(vault, &expected_vault, "vault")?;
+        let (expected_mint, _) = find_reward_mint_address(program_id);
+        expect_address(reward_mint, &expected_mint, "reward_mint")?;
+        let (expected_authority, reward_authority_bump) = find_reward_authority_address(program_id);
+        expect_address(reward_authority, &expected_authority, "reward_authority")?;
+        let expected_token_account =
+            spl_associated_token_account::get_associated_token_address(user.key, reward_mint.key);
+        expect_address(user_token_account, &expected_token_account, "user_token_account")?;
+        let (expected_claim_record, claim_record_bump) =
+            ClaimRecord::find_address(program_id, campaign.key, user.key);
+        expect_address(claim_record, &expected_claim_record, "claim_record")?;
+
+        Ok(Self {
+            user,
+            attestor,
+            campaign,
+            vault,
+            user_token_account,
+            reward_mint,
+            reward_authority,
+            system_program,
+            token_program,
+            associated_token_program,
+            claim_record,
+            reward_authority_bump,
+            claim_record_bump,
+        })
+    }
+}
+
+/// `CreateCampaign`: kampanya ve kasa henüz oluşturulmamış, sistem sahipli hesaplardır.
+pub struct CreateCampaignAccounts<'a, 'b> {
+    pub advertiser: &'a AccountInfo<'b>,
+    pub campaign: &'a AccountInfo<'b>,
+    pub vault: &'a AccountInfo<'b>,
+    pub system_program: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> CreateCampaignAccounts<'a, 'b> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let mut parser = AccountParser::new(accounts);
+        Ok(Self {
+            advertiser: parser.next_signer_writable("advertiser")?,
+            campaign: parser.next_owned("campaign", &system_program::id(), true)?,
+            vault: parser.next_owned("vault", &system_program::id(), true)?,
+            system_program: parser.next_program("system_program", &system_program::id())?,
+        })
+    }
+}
+
+/// `UpdateCampaign`, `PauseCampaign`.
+pub struct CampaignAuthorityAccounts<'a, 'b> {
+    pub advertiser: &'a AccountInfo<'b>,
+    pub campaign: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> CampaignAuthorityAccounts<'a, 'b> {
+    pub fn parse(program_id: &Pubkey, accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let mut parser = AccountParser::new(accounts);
+        Ok(Self {
+            advertiser: parser.next_signer("advertiser")?,
+            campaign: parser.next_owned("campaign", program_id, true)?,
+        })
+    }
+}
+
+/// `CloseCampaign`: kampanya ve kasa kapatılır, lamport'lar reklam verene döner.
+pub struct CloseCampaignAccounts<'a, 'b> {
+    pub advertiser: &'a AccountInfo<'b>,
+    pub campaign: &'a AccountInfo<'b>,
+    pub vault: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> CloseCampaignAccounts<'a, 'b> {
+    pub fn parse(program_id: &Pubkey, accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let mut parser = AccountParser::new(accounts);
+        let advertiser = parser.next_signer_writable("advertiser")?;
+        let campaign = parser.next_owned("campaign", program_id, true)?;
+        let vault = parser.next_owned("vault", program_id, true)?;
+
+        let (expected_vault, _) = Campaign::find_vault_address(program_id, campaign.key);
+        expect_address(vault, &expected_vault, "vault")?;
+
+        Ok(Self {
+            advertiser,
+            campaign,
+            vault,
+        })
+    }
+}
+
+pub struct FundCampaignAccounts<'a, 'b> {
+    pub advertiser: &'a AccountInfo<'b>,
+    pub campaign: &'a AccountInfo<'b>,
+    pub vault: &'a AccountInfo<'b>,
+    pub system_program: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> FundCampaignAccounts<'a, 'b> {
+    pub fn parse(program_id: &Pubkey, accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let mut parser = AccountParser::new(accounts);
+        let advertiser = parser.next_signer_writable("advertiser")?;
+        let campaign = parser.next_owned("campaign", program_id, true)?;
+        let vault = parser.next_owned("vault", program_id, true)?;
+        let system_program = parser.next_program("system_program", &system_program::id())?;
+
+        let (expected_vault, _) = Campaign::find_vault_address(program_id, campaign.key);
+        expect_address(vault, &expected_vault, "vault")?;
+
+        Ok(Self {
+            advertiser,
+            campaign,
+            vault,
+            system_program,
+        })
+    }
+}
+
+pub struct InitializeRewardMintAccounts<'a, 'b> {
+    pub payer: &'a AccountInfo<'b>,
+    pub reward_mint: &'a AccountInfo<'b>,
+    pub reward_authority: &'a AccountInfo<'b>,
+    pub system_program: &'a AccountInfo<'b>,
+    pub token_program: &'a AccountInfo<'b>,
+    pub reward_mint_bump: u8,
+    pub reward_authority_bump: u8,
+}
+
+impl<'a, 'b> InitializeRewardMintAccounts<'a, 'b> {
+    pub fn parse(program_id: &Pubkey, accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let mut parser = AccountParser::new(accounts);
+        let payer = parser.next_signer_writable("payer")?;
+        let reward_mint = parser.next_owned("reward_mint", &system_program::id(), true)?;
+        let reward_authority = parser.next_owned("reward_authority", &system_program::id(), true)?;
+        let system_program = parser.next_program("system_program", &system_program::id())?;
+        let token_program = parser.next_program("token_program", &spl_token::id())?;
+
+        let (expected_mint, reward_mint_bump) = find_reward_mint_address(program_id);
+        expect_address(reward_mint, &expected_mint, "reward_mint")?;
+        let (expected_authority, reward_authority_bump) = find_reward_authority_address(program_id);
+        expect_address(reward_authority, &expected_authority, "reward_authority")?;
+
+        Ok(Self {
+            payer,
+            reward_mint,
+            reward_authority,
+            system_program,
+            token_program,
+            reward_mint_bump,
+            reward_authority_bump,
+        })
+    }
+}
+
+/// `RedeemBlinks`: kullanıcı Blinks yakar, karşılığı yetkili hesaptan ödenir.
+pub struct RedeemBlinksAccounts<'a, 'b> {
+    pub user: &'a AccountInfo<'b>,
+    pub user_token_account: &'a AccountInfo<'b>,
+    pub reward_mint: &'a AccountInfo<'b>,
+    pub reward_authority: &'a AccountInfo<'b>,
+    pub token_program: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> RedeemBlinksAccounts<'a, 'b> {
+    pub fn parse(program_id: &Pubkey, accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let mut parser = AccountParser::new(accounts);
+        let user = parser.next_signer_writable("user")?;
+        let user_token_account = parser.next_owned("user_token_account", &spl_token::id(), true)?;
+        let reward_mint = parser.next_owned("reward_mint", &spl_token::id(), true)?;
+        let reward_authority = parser.next_owned("reward_authority", program_id, true)?;
+        let token_program = parser.next_program("token_program", &spl_token::id())?;
+
+        let (expected_mint, _) = find_reward_mint_address(program_id);
+        expect_address(reward_mint, &expected_mint, "reward_mint")?;
+        let (expected_authority, _) = find_reward_authority_address(program_id);
+        expect_address(reward_authority, &expected_authority, "reward_authority")?;
+        let expected_token_account =
+            spl_associated_token_account::get_associated_token_address(user.key, reward_mint.key);
+        expect_address(user_token_account, &expected_token_account, "user_token_account")?;
+
+        Ok(Self {
+            user,
+            user_token_account,
+            reward_mint,
+            reward_authority,
+            token_program,
+        })
+    }
+}
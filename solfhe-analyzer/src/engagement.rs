@@ -0,0 +1,98 @@
+// Kullanıcının reklam etkileşim raporu ve ödül hesaplaması
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::program_error::ProgramError;
+
+use crate::error::SolfheError;
+use crate::state::Campaign;
+
+pub const ENGAGEMENT_REPORT_VERSION: u8 = 1;
+
+pub const MAX_IMPRESSIONS_PER_REPORT: u32 = 1_000;
+/// Bir slotta (~400 ms) en fazla bir gösterim sayılır; kısa bir aralık büyük bir rapor taşıyamaz.
+pub const MAX_IMPRESSIONS_PER_SLOT: u64 = 1;
+pub const MAX_DWELL_SECS_PER_IMPRESSION: u32 = 300;
+/// Solana'nın hedeflenen slot süresi; rapor aralığının duvar saati karşılığı için kullanılır.
+pub const MS_PER_SLOT: u64 = 400;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct EngagementReport {
+    pub version: u8,
+    pub campaign_id: u64,
+    pub impressions: u32,
+    pub clicks: u32,
+    pub dwell_time_secs: u32,
+    pub start_slot: u64,
+    pub end_slot: u64,
+}
+
+impl EngagementReport {
+    /// Sürüm baytı ilk bayttır; bilinmeyen bir sürüm yapı çözülmeden reddedilir.
+    pub fn decode(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.first() != Some(&ENGAGEMENT_REPORT_VERSION) {
+            return Err(SolfheError::UnsupportedReportVersion.into());
+        }
+        Self::try_from_slice(data).map_err(|_| SolfheError::InvalidEngagementReport.into())
+    }
+
+    pub fn validate(&self, campaign: &Campaign, current_slot: u64) -> Result<(), ProgramError> {
+        if self.version != ENGAGEMENT_REPORT_VERSION {
+            return Err(SolfheError::UnsupportedReportVersion.into());
+        }
+        if self.campaign_id != campaign.campaign_id {
+            return Err(SolfheError::InvalidEngagementReport.into());
+        }
+        if self.impressions == 0 || self.impressions > MAX_IMPRESSIONS_PER_REPORT {
+            return Err(SolfheError::InvalidEngagementReport.into());
+        }
+        if self.clicks > self.impressions {
+            return Err(SolfheError::InvalidEngagementReport.into());
+        }
+        let max_dwell = u64::from(self.impressions) * u64::from(MAX_DWELL_SECS_PER_IMPRESSION);
+        if u64::from(self.dwell_time_secs) > max_dwell {
+            return Err(SolfheError::InvalidEngagementReport.into());
+        }
+
+        // Rapor aralığı geçmişte ve kampanya penceresinin içinde olmalı
+        if self.start_slot > self.end_slot || self.end_slot > current_slot {
+            return Err(SolfheError::InvalidEngagementReport.into());
+        }
+        if self.start_slot < campaign.start_slot || self.end_slot > campaign.end_slot {
+            return Err(SolfheError::InvalidEngagementReport.into());
+        }
+        let span_slots = self.end_slot - self.start_slot + 1;
+        if u64::from(self.impressions) > span_slots.saturating_mul(MAX_IMPRESSIONS_PER_SLOT) {
+            return Err(SolfheError::InvalidEngagementReport.into());
+        }
+        let span_ms = span_slots.saturating_mul(MS_PER_SLOT);
+        let span_secs = span_ms / 1_000 + u64::from(span_ms % 1_000 != 0);
+        if u64::from(self.dwell_time_secs) > span_secs {
+            return Err(SolfheError::InvalidEngagementReport.into());
+        }
+        Ok(())
+    }
+}
+
+/// Ödül, kampanyanın gösterim, tıklama ve izlenme süresi fiyatlarıyla hesaplanır.
+pub fn calculate_reward(
+    report: &EngagementReport,
+    campaign: &Campaign,
+    current_slot: u64,
+) -> Result<u64, ProgramError> {
+    report.validate(campaign, current_slot)?;
+
+    let impressions = u64::from(report.impressions)
+        .checked_mul(campaign.bid_per_impression)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let clicks = u64::from(report.clicks)
+        .checked_mul(campaign.bid_per_click)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let dwell = u64::from(report.dwell_time_secs)
+        .checked_mul(campaign.reward_per_dwell_second)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    impressions
+        .checked_add(clicks)
+        .and_then(|sum| sum.checked_add(dwell))
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
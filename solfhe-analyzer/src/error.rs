@@ -0,0 +1,95 @@
+// Programa özgü hata kodları; `ProgramError::Custom` olarak döner
+
+use solana_program::program_error::ProgramError;
+use std::fmt;
+
+/// Kodlar sabittir: yeni varyantlar sona eklenir, mevcut numaralar değişmez.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolfheError {
+    MissingAccount = 0,
+    AccountNotSigner = 1,
+    AccountNotWritable = 2,
+    IncorrectAccountOwner = 3,
+    IncorrectProgramAccount = 4,
+    InvalidAccountAddress = 5,
+    Unauthorized = 6,
+    InvalidCampaignParams = 7,
+    CampaignNotLive = 8,
+    CampaignExhausted = 9,
+    UnsupportedReportVersion = 10,
+    InvalidEngagementReport = 11,
+    ReportAlreadyClaimed = 12,
+    ProfileHashMismatch = 13,
+    UnregisteredStateTree = 14,
+    StateTreeRegistryFull = 15,
+    MerkleContextMismatch = 16,
+    InvalidDiscriminator = 17,
+    InsufficientRewardBacking = 18,
+    MissingReportAttestation = 19,
+}
+
+impl SolfheError {
+    const ALL: [SolfheError; 20] = [
+        SolfheError::MissingAccount,
+        SolfheError::AccountNotSigner,
+        SolfheError::AccountNotWritable,
+        SolfheError::IncorrectAccountOwner,
+        SolfheError::IncorrectProgramAccount,
+        SolfheError::InvalidAccountAddress,
+        SolfheError::Unauthorized,
+        SolfheError::InvalidCampaignParams,
+        SolfheError::CampaignNotLive,
+        SolfheError::CampaignExhausted,
+        SolfheError::UnsupportedReportVersion,
+        SolfheError::InvalidEngagementReport,
+        SolfheError::ReportAlreadyClaimed,
+        SolfheError::ProfileHashMismatch,
+        SolfheError::UnregisteredStateTree,
+        SolfheError::StateTreeRegistryFull,
+        SolfheError::MerkleContextMismatch,
+        SolfheError::InvalidDiscriminator,
+        SolfheError::InsufficientRewardBacking,
+        SolfheError::MissingReportAttestation,
+    ];
+
+    /// `ProgramError::Custom` kodunu tekrar enum'a çevirir (istemci tarafı için).
+    pub fn from_code(code: u32) -> Option<Self> {
+        Self::ALL.iter().copied().find(|e| *e as u32 == code)
+    }
+}
+
+impl fmt::Display for SolfheError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            SolfheError::MissingAccount => "a required account was not provided",
+            SolfheError::AccountNotSigner => "an account that must sign did not sign",
+            SolfheError::AccountNotWritable => "an account that must be writable is read-only",
+            SolfheError::IncorrectAccountOwner => "an account is owned by the wrong program",
+            SolfheError::IncorrectProgramAccount => "a program account does not match the expected program",
+            SolfheError::InvalidAccountAddress => "an account address does not match its expected PDA",
+            SolfheError::Unauthorized => "the signer is not the campaign's advertiser or the program's admin",
+            SolfheError::InvalidCampaignParams => "campaign keywords, bids, slots or budget are invalid",
+            SolfheError::CampaignNotLive => "the campaign is paused or outside its slot window",
+            SolfheError::CampaignExhausted => "the campaign's remaining budget cannot cover the reward",
+            SolfheError::UnsupportedReportVersion => "the engagement report version is not supported",
+            SolfheError::InvalidEngagementReport => "the engagement report failed validation",
+            SolfheError::ReportAlreadyClaimed => "the engagement report overlaps an already claimed range",
+            SolfheError::ProfileHashMismatch => "the compressed data does not match its data hash",
+            SolfheError::UnregisteredStateTree => "the state tree and nullifier queue are not registered in the config",
+            SolfheError::StateTreeRegistryFull => "the config cannot register more state trees",
+            SolfheError::MerkleContextMismatch => "the merkle context does not match the supplied tree and queue accounts",
+            SolfheError::InvalidDiscriminator => "the compressed account does not hold the expected payload type",
+            SolfheError::InsufficientRewardBacking => "the reward authority does not hold enough lamports to redeem the Blinks",
+            SolfheError::MissingReportAttestation => "the engagement report is not co-signed by the campaign's advertiser or attestor",
+        };
+        f.write_str(reason)
+    }
+}
+
+impl std::error::Error for SolfheError {}
+
+impl From<SolfheError> for ProgramError {
+    fn from(e: SolfheError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,309 @@
+// Light system programının `invoke_cpi` talimatı için hesap listesi ve veri kodlayıcı
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use light_sdk::{
+    compressed_account::CompressedAccount,
+    constants::{PROGRAM_ID_ACCOUNT_COMPRESSION, PROGRAM_ID_LIGHT_SYSTEM, PROGRAM_ID_NOOP},
+    merkle_context::{MerkleContext, QueueIndex},
+    proof::CompressedProof,
+};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+/// Anchor talimat ayırıcısı: sha256("global:invoke_cpi")[..8].
+pub const INVOKE_CPI_DISCRIMINATOR: [u8; 8] = [49, 212, 191, 129, 39, 194, 43, 196];
+
+pub const CPI_AUTHORITY_SEED: &[u8] = b"cpi_authority";
+
+/// Girdi hesabının Merkle bağlamı; ağaç ve kuyruk, kalan hesaplar içindeki sıralarıyla yazılır.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
+pub struct PackedMerkleContext {
+    pub merkle_tree_pubkey_index: u8,
+    pub nullifier_queue_pubkey_index: u8,
+    pub leaf_index: u32,
+    pub queue_index: Option<QueueIndex>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct PackedCompressedAccountWithMerkleContext {
+    pub compressed_account: CompressedAccount,
+    pub merkle_context: PackedMerkleContext,
+    pub root_index: u16,
+    pub read_only: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct OutputCompressedAccountWithPackedContext {
+    pub compressed_account: CompressedAccount,
+    pub merkle_tree_index: u8,
+}
+
+/// Yeni bir sıkıştırılmış adres oluşturmak için çağıranın verdiği parametreler.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NewAddressParams {
+    pub seed: [u8; 32],
+    pub address_queue_pubkey: Pubkey,
+    pub address_merkle_tree_pubkey: Pubkey,
+    pub address_merkle_tree_root_index: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
+pub struct NewAddressParamsPacked {
+    pub seed: [u8; 32],
+    pub address_queue_account_index: u8,
+    pub address_merkle_tree_account_index: u8,
+    pub address_merkle_tree_root_index: u16,
+}
+
+/// Talimat verisi: ayırıcı + Borsh `Vec<u8>` olarak kodlanmış argüman.
+fn encode_instruction_data(discriminator: [u8; 8], args: &impl BorshSerialize) -> Result<Vec<u8>, ProgramError> {
+    let inputs = args.try_to_vec().map_err(|_| ProgramError::InvalidInstructionData)?;
+    let mut data = discriminator.to_vec();
+    inputs
+        .serialize(&mut data)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+    Ok(data)
+}
+
+fn decode_instruction_data<T: BorshDeserialize>(expected: [u8; 8], data: &[u8]) -> Result<T, ProgramError> {
+    let (discriminator, rest) = data.split_at(data.len().min(8));
+    if discriminator != expected {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let inputs = Vec::<u8>::try_from_slice(rest).map_err(|_| ProgramError::InvalidInstructionData)?;
+    T::try_from_slice(&inputs).map_err(|_| ProgramError::InvalidInstructionData)
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
+pub struct CompressedCpiContext {
+    pub set_context: bool,
+    pub first_set_context: bool,
+    pub cpi_context_account_index: u8,
+}
+
+/// `invoke_cpi` talimatının argümanı; alan sırası Light system programıyla aynıdır.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct InstructionDataInvokeCpi {
+    pub proof: Option<CompressedProof>,
+    pub new_address_params: Vec<NewAddressParamsPacked>,
+    pub input_compressed_accounts_with_merkle_context: Vec<PackedCompressedAccountWithMerkleContext>,
+    pub output_compressed_accounts: Vec<OutputCompressedAccountWithPackedContext>,
+    pub relay_fee: Option<u64>,
+    pub compress_or_decompress_lamports: Option<u64>,
+    pub is_compress: bool,
+    pub cpi_context: Option<CompressedCpiContext>,
+}
+
+impl InstructionDataInvokeCpi {
+    pub fn encode(&self) -> Result<Vec<u8>, ProgramError> {
+        encode_instruction_data(INVOKE_CPI_DISCRIMINATOR, self)
+    }
+
+    pub fn decode(data: &[u8]) -> Result<Self, ProgramError> {
+        decode_instruction_data(INVOKE_CPI_DISCRIMINATOR, data)
+    }
+}
+
+/// Account compression programına kayıtlı Light system programı hesabı.
+pub fn find_registered_program_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROGRAM_ID_LIGHT_SYSTEM.as_ref()], &PROGRAM_ID_ACCOUNT_COMPRESSION)
+}
+
+pub fn find_account_compression_authority_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CPI_AUTHORITY_SEED], &PROGRAM_ID_LIGHT_SYSTEM)
+}
+
+/// Çağıran programın Light'a `invoke_cpi` ile imza attığı PDA (seeds `["cpi_authority"]`,
+/// çağıran programın altında). Light, program sahipli hesapları yalnızca bu imzayla kabul eder.
+pub fn find_cpi_authority_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CPI_AUTHORITY_SEED], program_id)
+}
+
+/// Pubkey'i kalan hesaplar listesine (yoksa) ekler ve sırasını döner.
+fn pack_pubkey(pubkey: &Pubkey, remaining_accounts: &mut Vec<Pubkey>) -> Result<u8, ProgramError> {
+    let index = match remaining_accounts.iter().position(|k| k == pubkey) {
+        Some(index) => index,
+        None => {
+            remaining_accounts.push(*pubkey);
+            remaining_accounts.len() - 1
+        }
+    };
+    u8::try_from(index).map_err(|_| ProgramError::InvalidArgument)
+}
+
+/// Program sahipli hesabın kayıtlı kökteki varlığını kanıtlayan `invoke_cpi`
+/// çağrısı. Hesap salt okunur girdidir: harcanmaz, nullifier kuyruğuna yazılmaz
+/// ve yeniden çıktı olarak yazılmaz. Light, hesabın hash'ini `root_index`'teki
+/// köke karşı `proof` ile doğrulamazsa çağrı başarısız olur.
+pub fn create_inclusion_check_instruction(
+    payer: &Pubkey,
+    invoking_program: &Pubkey,
+    compressed_account: &CompressedAccount,
+    merkle_context: &MerkleContext,
+    root_index: u16,
+    proof: CompressedProof,
+) -> Result<Instruction, ProgramError> {
+    create_invoke_cpi_instruction(
+        payer,
+        invoking_program,
+        std::slice::from_ref(compressed_account),
+        &[*merkle_context],
+        &[root_index],
+        true,
+        &[],
+        &[],
+        &[],
+        Some(proof),
+        false,
+    )
+}
+
+/// Girdi, çıktı ve yeni adres parametrelerinin paketlenmiş hali; pubkey'ler
+/// `remaining_accounts` içindeki sıralarıyla yazılır.
+struct PackedAccounts {
+    inputs: Vec<PackedCompressedAccountWithMerkleContext>,
+    outputs: Vec<OutputCompressedAccountWithPackedContext>,
+    new_addresses: Vec<NewAddressParamsPacked>,
+    remaining_accounts: Vec<Pubkey>,
+}
+
+/// Girdi hesapları, Merkle bağlamları ve kök indeksleri aynı uzunlukta olmalıdır;
+/// her çıktı hesabı `output_merkle_tree_pubkeys` içindeki aynı sıradaki ağaca yazılır.
+#[allow(clippy::too_many_arguments)]
+fn pack_accounts(
+    input_compressed_accounts: &[CompressedAccount],
+    input_merkle_contexts: &[MerkleContext],
+    root_indices: &[u16],
+    read_only: bool,
+    output_compressed_accounts: &[CompressedAccount],
+    output_merkle_tree_pubkeys: &[Pubkey],
+    new_address_params: &[NewAddressParams],
+    sort: bool,
+) -> Result<PackedAccounts, ProgramError> {
+    if input_compressed_accounts.len() != input_merkle_contexts.len()
+        || input_compressed_accounts.len() != root_indices.len()
+        || output_compressed_accounts.len() != output_merkle_tree_pubkeys.len()
+    {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut remaining_accounts = Vec::new();
+
+    let mut inputs = Vec::with_capacity(input_compressed_accounts.len());
+    for ((account, context), root_index) in input_compressed_accounts
+        .iter()
+        .zip(input_merkle_contexts)
+        .zip(root_indices)
+    {
+        inputs.push(PackedCompressedAccountWithMerkleContext {
+            compressed_account: account.clone(),
+            merkle_context: PackedMerkleContext {
+                merkle_tree_pubkey_index: pack_pubkey(&context.merkle_tree_pubkey, &mut remaining_accounts)?,
+                nullifier_queue_pubkey_index: pack_pubkey(&context.nullifier_queue_pubkey, &mut remaining_accounts)?,
+                leaf_index: context.leaf_index,
+                queue_index: context.queue_index,
+            },
+            root_index: *root_index,
+            read_only,
+        });
+    }
+
+    let mut outputs = Vec::with_capacity(output_compressed_accounts.len());
+    for (account, merkle_tree) in output_compressed_accounts.iter().zip(output_merkle_tree_pubkeys) {
+        outputs.push(OutputCompressedAccountWithPackedContext {
+            compressed_account: account.clone(),
+            merkle_tree_index: pack_pubkey(merkle_tree, &mut remaining_accounts)?,
+        });
+    }
+    // Light, çıktıları ağaç sırasına göre gruplanmış olarak bekler
+    if sort {
+        outputs.sort_by_key(|o| o.merkle_tree_index);
+    }
+
+    let mut new_addresses = Vec::with_capacity(new_address_params.len());
+    for params in new_address_params {
+        new_addresses.push(NewAddressParamsPacked {
+            seed: params.seed,
+            address_queue_account_index: pack_pubkey(&params.address_queue_pubkey, &mut remaining_accounts)?,
+            address_merkle_tree_account_index: pack_pubkey(
+                &params.address_merkle_tree_pubkey,
+                &mut remaining_accounts,
+            )?,
+            address_merkle_tree_root_index: params.address_merkle_tree_root_index,
+        });
+    }
+
+    Ok(PackedAccounts {
+        inputs,
+        outputs,
+        new_addresses,
+        remaining_accounts,
+    })
+}
+
+/// Light system programının `invoke_cpi` talimatını oluşturur. Yetkili,
+/// `invoking_program`'ın CPI yetkili PDA'sıdır; girdi ve çıktı hesaplarının sahibi
+/// `invoking_program` olmalı ve çağıran program talimatı `invoke_signed` ile imzalamalıdır.
+#[allow(clippy::too_many_arguments)]
+pub fn create_invoke_cpi_instruction(
+    payer: &Pubkey,
+    invoking_program: &Pubkey,
+    input_compressed_accounts: &[CompressedAccount],
+    input_merkle_contexts: &[MerkleContext],
+    root_indices: &[u16],
+    read_only: bool,
+    output_compressed_accounts: &[CompressedAccount],
+    output_merkle_tree_pubkeys: &[Pubkey],
+    new_address_params: &[NewAddressParams],
+    proof: Option<CompressedProof>,
+    sort: bool,
+) -> Result<Instruction, ProgramError> {
+    let packed = pack_accounts(
+        input_compressed_accounts,
+        input_merkle_contexts,
+        root_indices,
+        read_only,
+        output_compressed_accounts,
+        output_merkle_tree_pubkeys,
+        new_address_params,
+        sort,
+    )?;
+
+    let data = InstructionDataInvokeCpi {
+        proof,
+        new_address_params: packed.new_addresses,
+        input_compressed_accounts_with_merkle_context: packed.inputs,
+        output_compressed_accounts: packed.outputs,
+        relay_fee: None,
+        compress_or_decompress_lamports: None,
+        is_compress: false,
+        cpi_context: None,
+    }
+    .encode()?;
+
+    // Sol havuzu, alıcı ve CPI bağlam hesabı kullanılmaz; yerlerine Light system programı geçer
+    let mut accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(find_cpi_authority_address(invoking_program).0, true),
+        AccountMeta::new_readonly(find_registered_program_address().0, false),
+        AccountMeta::new_readonly(PROGRAM_ID_NOOP, false),
+        AccountMeta::new_readonly(find_account_compression_authority_address().0, false),
+        AccountMeta::new_readonly(PROGRAM_ID_ACCOUNT_COMPRESSION, false),
+        AccountMeta::new_readonly(*invoking_program, false),
+        AccountMeta::new_readonly(PROGRAM_ID_LIGHT_SYSTEM, false),
+        AccountMeta::new_readonly(PROGRAM_ID_LIGHT_SYSTEM, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(PROGRAM_ID_LIGHT_SYSTEM, false),
+    ];
+    accounts.extend(packed.remaining_accounts.iter().map(|k| AccountMeta::new(*k, false)));
+
+    Ok(Instruction {
+        program_id: PROGRAM_ID_LIGHT_SYSTEM,
+        accounts,
+        data,
+    })
+}
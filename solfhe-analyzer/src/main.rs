@@ -9,14 +9,22 @@ This document prepared by `@Virjilakrum🏗️`` explains in technical detail ho
 - It is an `enum` that defines the different types of instructions that the program can process.
 - `AnalyzeLinks`: Analyzes the given links and determines the keywords.
 - `CompressAndTransfer`: Compresses and transfers the data.
-- `RetrieveAndDecompress`: Decompresses and returns the compressed data.
+- `RetrieveAndDecompress`: Verifies a compressed account against its state tree root and records its data as a memo.
 - `ServeAd`: Serves advertisements according to the user profile.
 - `RewardUser`: Analyzes the user interaction and gives rewards accordingly.
+- `CreateCampaign`, `UpdateCampaign`, `PauseCampaign`, `CloseCampaign`: Manage the advertiser-owned campaign accounts (PDA, seeds `["campaign", advertiser, campaign_id]`) that hold target keywords, budget, bid per impression, the start/end slot window and the attestor (an oracle key, defaulting to the advertiser) that co-signs engagement reports.
+- `FundCampaign`: Moves lamports into the campaign's escrow vault (PDA, seeds `["vault", campaign]`).
+- `CloseCampaign` closes both the campaign account and its vault: the unspent deposit and the rent of both accounts go back to the advertiser, the data is zeroed and the accounts are handed back to the system program.
+- `InitializeConfig`, `RegisterStateTree`, `RemoveStateTree`: Manage the program config account (PDA, seeds `["config"]`) that lists the Light state merkle trees and nullifier queues compressed accounts may be written to. Only the program's upgrade authority, read from its `ProgramData` account, can initialize the config and become its admin.
+- `InitializeRewardMint`: Creates the SPL "Blinks" reward mint (PDA, seeds `["blinks_mint"]`) whose mint authority is the `["reward_authority"]` PDA.
+- `RedeemBlinks`: Burns the user's Blinks and pays out the same number of lamports from the reward authority PDA, which holds the lamports backing the minted supply.
 
 3. `process_instruction` Function:
 - It is the main instruction processor of the program. It determines the appropriate `SolfheInstruction` variant by deserializing the incoming `instruction_data`.
 - Each instruction variant is directed to the corresponding special function.
 - For example, the `AnalyzeLinks` variant goes to the `analyze_links` function and analyzes the links.
+- Every handler first parses its accounts into a typed struct from the `accounts` module (one struct per instruction). Missing, non-signer, read-only, wrong-owner or wrong-address accounts are rejected with a `SolfheError` custom code and a log line naming the account.
+- Domain failures (for example `CampaignExhausted`, `ReportAlreadyClaimed`, `ProfileHashMismatch`, `Unauthorized`) are also `SolfheError` variants with stable `ProgramError::Custom` codes; `client::decode_error` turns a failed transaction back into the enum.
 
 4. `analyze_links` Function:
 - Extracts the keywords from the given URLs and calculates their number.
@@ -39,16 +47,20 @@ This document prepared by `@Virjilakrum🏗️`` explains in technical detail ho
 - Compresses the given data using the `CompressedAccount` structure and transfers it to the Solana chain.
 - It uses the `CompressedAccount` and `CompressedAccountData` structures provided by `Light SDK`.
 - After the data is compressed, a Merkle tree is created using `MerkleContext` and the accuracy of the data is guaranteed.
-- The compressed data is transferred to other accounts on the chain using the `create_invoke_instruction` function.
+- The caller passes the state merkle tree and nullifier queue accounts; the pair must be registered in the program config, and the invoke instruction references exactly those accounts.
+- The account is owned by this program, so it is written through the Light system program's `invoke_cpi`, built with `create_invoke_cpi_instruction` and signed by the program's CPI authority PDA.
+- The caller names the payload type (`PayloadKind`: user profile, analysis result or engagement report) and the account's discriminator is set to that type's 8-byte discriminator, the first 8 bytes of `sha256("solfhe:<TypeName>")`. `serve_ad` only accepts user profiles, and `retrieve_and_decompress` rejects accounts that do not hold the type the caller asked for.
 
 7. `retrieve_and_decompress` Function:
 - It retrieves and decompresses the compressed data on the chain.
-- It extracts the data from the `CompressedAccount` structure and returns it to its original form.
+- The instruction carries the `CompressedAccount`, its `MerkleContext`, the root index and a `CompressedProof`. The merkle context must name the registered tree and queue accounts that were passed in, and the account must be owned by this program.
+- Before the data is used, the account is passed to the Light system program's `invoke_cpi` as a read-only input with no outputs, signed by the program's CPI authority PDA. Light checks its hash against the tree root with the proof, so the call fails unless the account is really in the tree; a read-only input is not nullified, so the account stays in the tree unchanged.
 - After this process, the data is saved on the chain using the `save_result_as_memo` function.
 
 8. `serve_ad` Function:
 - The user profile is received as compressed data and the advertisement serving process is performed.
 - Ad targeting operations are performed according to the user's profile data and user privacy is protected using the `CompressedAccount` data.
+- Candidate campaign accounts are passed after the payer; the live campaign whose target keywords best overlap the profile's top words wins, ties going to the higher bid.
 - This allows advertisers to reach specific target audiences.
 
 9. `reward_user` Function:
@@ -56,19 +68,21 @@ This document prepared by `@Virjilakrum🏗️`` explains in technical detail ho
 - Allows the user to be rewarded for their interactions with ads.
 - The amount of reward the user will receive based on the interaction data is calculated with the `calculate_reward` function.
 
-- A certain amount of lamport reward is transferred to the user using the `solana_program::system_instruction::transfer` function.
+- The reward is paid by the advertiser: it is debited from the campaign's program-owned escrow vault, which only the advertiser funds through `FundCampaign`. The campaign's `spent` counter never exceeds its budget or its deposits, and whatever is left in the vault goes back to the advertiser when the campaign is closed.
+- The user receives Blinks rather than lamports: one base unit of Blinks is minted to the user's associated token account (created if missing) for every lamport debited from the vault, and the debited lamports move to the program-owned reward authority PDA as backing for the minted supply. Holders get them back one lamport per base unit by burning Blinks with `RedeemBlinks`.
 
 10. `calculate_reward` Function:
 
 - Calculates the amount of reward based on the user's interactions with ads.
-- An `engagement_score` is calculated by collecting the interaction data and this score is multiplied by a certain coefficient to determine the amount of reward.
-- This method allows the user to receive more rewards as their interaction with ads increases.
+- The engagement data is a Borsh-encoded, versioned `EngagementReport` (campaign id, impression count, click count, dwell time and slot range). Each field is validated against per-report limits and the campaign's slot window before any reward is computed; a report cannot claim more than `MAX_IMPRESSIONS_PER_SLOT` impressions per slot of its range.
+- The user cannot vouch for their own report: `RewardUser` must also be signed by the campaign's advertiser or attestor. Their transaction signature covers the instruction data, so it is a co-signature over the exact report being paid.
+- The reward is `impressions * bid_per_impression + clicks * bid_per_click + dwell_time_secs * reward_per_dwell_second`, using the campaign's configured rates and overflow-checked arithmetic.
 
-11. `create_invoke_instruction` Function:
+11. `create_invoke_cpi_instruction` Function (`light` module):
 
-- Creates the `Instruction` structure required for the processing and transfer of compressed data.
-- Necessary accounts such as `payer` and `authority` are determined and contexts related to Merkle trees are added.
-- ZK proofs (`CompressedProof`) and parameters such as whether the data will be compressed or not are used in this process.
+- Creates the `Instruction` structure required for the processing and transfer of compressed data. Every compressed account this program writes or reads is owned by the program, so Light is always called through `invoke_cpi`.
+- Builds the Light system program's `invoke_cpi` account list: `payer`, the program's CPI authority PDA, the registered program PDA, the noop program, the account compression authority and program, the invoking program, the unused sol pool, decompression recipient and CPI context slots, the system program, and finally the merkle trees and queues as remaining accounts.
+- The instruction data is the `invoke_cpi` discriminator followed by a Borsh-encoded `InstructionDataInvokeCpi`: the proof, new address params, input accounts with packed merkle contexts (account indices instead of pubkeys) and root indices, and output accounts with their tree index (optionally sorted by tree). Inputs can be marked read-only so they are verified but not spent.
 
 12. `extract_keywords` Fonksiyonu:
     - Verilen URL'yi analiz ederek 3 karakterden uzun olan anahtar kelimeleri çıkarır.
@@ -98,32 +112,92 @@ use solana_program::{
     pubkey::Pubkey,
     msg,
     program_error::ProgramError,
-    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+    program_pack::Pack,
+    system_instruction,
     system_program,
-    sysvar::{rent::Rent, Sysvar},
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
 };
 use borsh::{BorshDeserialize, BorshSerialize};
 use light_sdk::{
     compressed_account::{CompressedAccount, CompressedAccountData},
     merkle_context::MerkleContext,
     proof::CompressedProof,
-    constants::PROGRAM_ID_LIGHT_TOKEN,
 };
 use spl_memo::build_memo;
 use serde_json::json;
 use sha2::{Sha256, Digest};
 use std::collections::HashMap;
 
+mod accounts;
+mod engagement;
+mod error;
+mod light;
+mod state;
+mod tokenizer;
+
+use accounts::{
+    CampaignAuthorityAccounts, CloseCampaignAccounts, CompressAndTransferAccounts, ConfigAdminAccounts,
+    CreateCampaignAccounts, FundCampaignAccounts, InitializeConfigAccounts,
+    InitializeRewardMintAccounts, MemoAccounts, RedeemBlinksAccounts, RetrieveAndDecompressAccounts,
+    RewardUserAccounts, ServeAdAccounts,
+};
+use error::SolfheError;
+
+use engagement::{calculate_reward, EngagementReport};
+use light::{create_inclusion_check_instruction, create_invoke_cpi_instruction};
+
+use state::{
+    validate_campaign_params, Campaign, CampaignStatus, ClaimRecord, PayloadKind, ProgramConfig,
+    RegisteredTree, CAMPAIGN_SEED, CLAIM_SEED, CONFIG_SEED, MAX_REGISTERED_TREES,
+    REWARD_AUTHORITY_SEED, REWARD_MINT_SEED, VAULT_SEED,
+};
+
 // Entrypoint tanımı
 entrypoint!(process_instruction);
 
 #[derive(BorshSerialize, BorshDeserialize)]
 enum SolfheInstruction {
     AnalyzeLinks { links: Vec<String> },
-    CompressAndTransfer { data: String },
-    RetrieveAndDecompress { signature: [u8; 64] },
+    CompressAndTransfer { data: String, kind: PayloadKind },
+    RetrieveAndDecompress {
+        compressed_account: CompressedAccount,
+        merkle_context: MerkleContext,
+        root_index: u16,
+        proof: CompressedProof,
+        kind: PayloadKind,
+    },
     ServeAd { user_profile: Vec<u8> },
     RewardUser { engagement_data: Vec<u8> },
+    CreateCampaign {
+        campaign_id: u64,
+        target_keywords: Vec<String>,
+        budget: u64,
+        bid_per_impression: u64,
+        bid_per_click: u64,
+        reward_per_dwell_second: u64,
+        start_slot: u64,
+        end_slot: u64,
+        attestor: Option<Pubkey>,
+    },
+    UpdateCampaign {
+        target_keywords: Option<Vec<String>>,
+        budget: Option<u64>,
+        bid_per_impression: Option<u64>,
+        bid_per_click: Option<u64>,
+        reward_per_dwell_second: Option<u64>,
+        start_slot: Option<u64>,
+        end_slot: Option<u64>,
+        attestor: Option<Pubkey>,
+    },
+    PauseCampaign { paused: bool },
+    CloseCampaign,
+    FundCampaign { amount: u64 },
+    InitializeRewardMint { decimals: u8 },
+    InitializeConfig,
+    RegisterStateTree { merkle_tree: Pubkey, nullifier_queue: Pubkey },
+    RemoveStateTree { merkle_tree: Pubkey },
+    RedeemBlinks { amount: u64 },
 }
 
 pub fn process_instruction(
@@ -136,20 +210,105 @@ pub fn process_instruction(
 
     match instruction {
         SolfheInstruction::AnalyzeLinks { links } => {
+            let accounts = MemoAccounts::parse(accounts)?;
             let result = analyze_links(&links)?;
-            save_result_as_memo(accounts, &result)
+            save_result_as_memo(accounts.signer, accounts.memo_program, &result)
         },
-        SolfheInstruction::CompressAndTransfer { data } => {
-            compress_and_transfer(program_id, accounts, &data)
+        SolfheInstruction::CompressAndTransfer { data, kind } => {
+            compress_and_transfer(program_id, accounts, &data, kind)
         },
-        SolfheInstruction::RetrieveAndDecompress { signature } => {
-            retrieve_and_decompress(program_id, accounts, &signature)
+        SolfheInstruction::RetrieveAndDecompress {
+            compressed_account,
+            merkle_context,
+            root_index,
+            proof,
+            kind,
+        } => {
+            retrieve_and_decompress(
+                program_id,
+                accounts,
+                compressed_account,
+                merkle_context,
+                root_index,
+                proof,
+                kind,
+            )
         },
         SolfheInstruction::ServeAd { user_profile } => {
             serve_ad(program_id, accounts, &user_profile)
         },
         SolfheInstruction::RewardUser { engagement_data } => {
-            reward_user(accounts, &engagement_data)
+            reward_user(program_id, accounts, &engagement_data)
+        },
+        SolfheInstruction::CreateCampaign {
+            campaign_id,
+            target_keywords,
+            budget,
+            bid_per_impression,
+            bid_per_click,
+            reward_per_dwell_second,
+            start_slot,
+            end_slot,
+            attestor,
+        } => create_campaign(
+            program_id,
+            accounts,
+            campaign_id,
+            target_keywords,
+            budget,
+            CampaignRates {
+                bid_per_impression,
+                bid_per_click,
+                reward_per_dwell_second,
+            },
+            start_slot,
+            end_slot,
+            attestor,
+        ),
+        SolfheInstruction::UpdateCampaign {
+            target_keywords,
+            budget,
+            bid_per_impression,
+            bid_per_click,
+            reward_per_dwell_second,
+            start_slot,
+            end_slot,
+            attestor,
+        } => update_campaign(
+            program_id,
+            accounts,
+            target_keywords,
+            budget,
+            bid_per_impression,
+            bid_per_click,
+            reward_per_dwell_second,
+            start_slot,
+            end_slot,
+            attestor,
+        ),
+        SolfheInstruction::PauseCampaign { paused } => {
+            pause_campaign(program_id, accounts, paused)
+        },
+        SolfheInstruction::CloseCampaign => {
+            close_campaign(program_id, accounts)
+        },
+        SolfheInstruction::FundCampaign { amount } => {
+            fund_campaign(program_id, accounts, amount)
+        },
+        SolfheInstruction::InitializeRewardMint { decimals } => {
+            initialize_reward_mint(program_id, accounts, decimals)
+        },
+        SolfheInstruction::InitializeConfig => {
+            initialize_config(program_id, accounts)
+        },
+        SolfheInstruction::RegisterStateTree { merkle_tree, nullifier_queue } => {
+            register_state_tree(program_id, accounts, merkle_tree, nullifier_queue)
+        },
+        SolfheInstruction::RemoveStateTree { merkle_tree } => {
+            remove_state_tree(program_id, accounts, merkle_tree)
+        },
+        SolfheInstruction::RedeemBlinks { amount } => {
+            redeem_blinks(program_id, accounts, amount)
         },
     }
 }
@@ -176,13 +335,16 @@ fn analyze_links(links: &[String]) -> Result<String, ProgramError> {
     Ok(result.to_string())
 }
 
-fn save_result_as_memo(accounts: &[AccountInfo], result: &str) -> ProgramResult {
-    let memo_program_id = spl_memo::id();
-    let memo_instruction = spl_memo::build_memo(result.as_bytes(), &[&accounts[0].key]);
+fn save_result_as_memo<'a>(
+    signer: &AccountInfo<'a>,
+    memo_program: &AccountInfo<'a>,
+    result: &str,
+) -> ProgramResult {
+    let memo_instruction = spl_memo::build_memo(result.as_bytes(), &[signer.key]);
     
     solana_program::program::invoke(
         &memo_instruction,
-        accounts,
+        &[signer.clone(), memo_program.clone()],
     )
 }
 
@@ -190,61 +352,85 @@ fn compress_and_transfer(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     data: &str,
+    kind: PayloadKind,
 ) -> ProgramResult {
+    let accounts = CompressAndTransferAccounts::parse(program_id, accounts)?;
+    accounts.light.check_registered_tree()?;
+
     let compressed_account = CompressedAccount {
         owner: *program_id,
         lamports: 0,
         address: None,
         data: Some(CompressedAccountData {
-            discriminator: [0; 8],
+            discriminator: kind.discriminator(),
             data: data.as_bytes().to_vec(),
             data_hash: hash_data(data),
         }),
     };
 
-    let merkle_tree_pubkey = Pubkey::new_unique();
-    let merkle_context = MerkleContext {
-        merkle_tree_pubkey,
-        nullifier_queue_pubkey: Pubkey::new_unique(),
-        leaf_index: 0,
-        queue_index: None,
-    };
-
-    let instruction = create_invoke_instruction(
-        accounts[0].key,
-        accounts[1].key,
+    // Yeni bir çıktı hesabı yazılır; girdi olmadığı için kanıt ve kök indeksi gerekmez.
+    // Hesap program sahipli olduğundan Light yalnızca programın CPI yetkilisinin imzasını kabul eder.
+    let instruction = create_invoke_cpi_instruction(
+        accounts.payer.key,
+        program_id,
+        &[],
         &[],
-        &[compressed_account],
-        &[merkle_context],
-        &[merkle_tree_pubkey],
-        &[0],
         &[],
-        None,
-        None,
         false,
+        &[compressed_account],
+        &[*accounts.light.merkle_tree.key],
+        &[],
         None,
         true,
-    );
-
-    solana_program::program::invoke(&instruction, accounts)?;
+    )?;
+    accounts.light.invoke_signed(&instruction, accounts.payer, accounts.remaining)?;
     
     msg!("Data compressed and transferred successfully");
     Ok(())
 }
 
 fn retrieve_and_decompress(
-    _program_id: &Pubkey,
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
-    _signature: &[u8; 64],
+    compressed_account: CompressedAccount,
+    merkle_context: MerkleContext,
+    root_index: u16,
+    proof: CompressedProof,
+    kind: PayloadKind,
 ) -> ProgramResult {
-    let compressed_account = CompressedAccount::try_from_slice(&accounts[2].data.borrow())
-        .map_err(|_| ProgramError::InvalidAccountData)?;
+    let accounts = RetrieveAndDecompressAccounts::parse(program_id, accounts)?;
+    accounts.light.check_registered_tree()?;
+    if merkle_context.merkle_tree_pubkey != *accounts.light.merkle_tree.key
+        || merkle_context.nullifier_queue_pubkey != *accounts.light.nullifier_queue.key
+    {
+        return Err(SolfheError::MerkleContextMismatch.into());
+    }
+    if compressed_account.owner != *program_id {
+        return Err(SolfheError::IncorrectAccountOwner.into());
+    }
+    if let Some(data) = &compressed_account.data {
+        kind.expect(&data.discriminator)?;
+    }
+
+    // Kanıt Light tarafından doğrulanmadan veri kullanılmaz
+    let instruction = create_inclusion_check_instruction(
+        accounts.signer.key,
+        program_id,
+        &compressed_account,
+        &merkle_context,
+        root_index,
+        proof,
+    )?;
+    accounts.light.invoke_signed(&instruction, accounts.signer, &[])?;
 
     if let Some(data) = compressed_account.data {
+        if hash_data(&data.data) != data.data_hash {
+            return Err(SolfheError::ProfileHashMismatch.into());
+        }
         let decompressed_data = String::from_utf8(data.data)
             .map_err(|_| ProgramError::InvalidAccountData)?;
         msg!("Decompressed data: {}", decompressed_data);
-        save_result_as_memo(accounts, &decompressed_data)?;
+        save_result_as_memo(accounts.signer, accounts.memo_program, &decompressed_data)?;
     } else {
         msg!("No data found in the compressed account");
     }
@@ -253,126 +439,700 @@ fn retrieve_and_decompress(
 }
 
 fn serve_ad(
-    _program_id: &Pubkey,
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     user_profile: &[u8],
 ) -> ProgramResult {
+    let accounts = ServeAdAccounts::parse(program_id, accounts)?;
+
     // ZK-compressed kullanıcı profili kullanarak reklam hedefleme işlemi
     let compressed_account = CompressedAccount::try_from_slice(user_profile)
         .map_err(|_| ProgramError::InvalidAccountData)?;
 
-    if let Some(data) = compressed_account.data {
-        msg!("Serving ad using user profile data...");
-        // Reklam hedefleme işlemleri buraya eklenebilir
-        // Örneğin, reklam verenlerin belirli hedef kitlelere reklamlarını sunması
-    } else {
-        msg!("No user profile data available");
+    let data = match compressed_account.data {
+        Some(data) => data,
+        None => {
+            msg!("No user profile data available");
+            return Ok(());
+        }
+    };
+    PayloadKind::UserProfile.expect(&data.discriminator)?;
+    if hash_data(&data.data) != data.data_hash {
+        return Err(SolfheError::ProfileHashMismatch.into());
+    }
+
+    let mut campaigns = Vec::new();
+    for account in accounts.campaigns {
+        if let Ok(campaign) = Campaign::unpack(&account.data.borrow()) {
+            campaigns.push((*account.key, campaign));
+        }
+    }
+
+    let profile = profile_keywords(&data.data);
+    let slot = Clock::get()?.slot;
+    match select_campaign(&campaigns, &profile, slot) {
+        Some((campaign_key, score)) => {
+            msg!(
+                "Serving ad from campaign {} to {} (match score {})",
+                campaign_key,
+                accounts.payer.key,
+                score
+            );
+        },
+        None => msg!("No live campaign matches the user profile"),
+    }
+    Ok(())
+}
+
+/// Sıkıştırılmış profil `analyze_links` çıktısıdır; `top_words` yoksa metnin
+/// kendisinden anahtar kelime çıkarılır.
+fn profile_keywords(data: &[u8]) -> Vec<(String, u64)> {
+    let text = String::from_utf8_lossy(data);
+    if let Ok(value) = serde_json::from_str::<serde_json::Value>(&text) {
+        if let Some(top_words) = value["top_words"].as_array() {
+            return top_words
+                .iter()
+                .filter_map(|entry| {
+                    let word = entry.get(0)?.as_str()?.to_lowercase();
+                    let count = entry.get(1)?.as_u64()?;
+                    Some((word, count))
+                })
+                .collect();
+        }
+    }
+    extract_keywords(&text).into_iter().map(|word| (word, 1)).collect()
+}
+
+fn select_campaign(
+    campaigns: &[(Pubkey, Campaign)],
+    profile: &[(String, u64)],
+    slot: u64,
+) -> Option<(Pubkey, u64)> {
+    let mut best: Option<(Pubkey, u64, u64)> = None;
+    for (key, campaign) in campaigns {
+        if !campaign.is_live(slot) || campaign.remaining_budget() < campaign.bid_per_impression {
+            continue;
+        }
+        let score: u64 = profile
+            .iter()
+            .filter(|(word, _)| campaign.target_keywords.iter().any(|k| k == word))
+            .map(|(_, count)| *count)
+            .fold(0u64, |acc, count| acc.saturating_add(count));
+        if score == 0 {
+            continue;
+        }
+        let better = match best {
+            Some((_, best_score, best_bid)) => {
+                (score, campaign.bid_per_impression) > (best_score, best_bid)
+            },
+            None => true,
+        };
+        if better {
+            best = Some((*key, score, campaign.bid_per_impression));
+        }
+    }
+    best.map(|(key, score, _)| (key, score))
+}
+
+fn normalize_keywords(target_keywords: Vec<String>) -> Vec<String> {
+    target_keywords.into_iter().map(|k| k.trim().to_lowercase()).collect()
+}
+
+/// Kampanyanın etkileşim başına ödeme oranları.
+struct CampaignRates {
+    bid_per_impression: u64,
+    bid_per_click: u64,
+    reward_per_dwell_second: u64,
+}
+
+fn create_campaign(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    campaign_id: u64,
+    target_keywords: Vec<String>,
+    budget: u64,
+    rates: CampaignRates,
+    start_slot: u64,
+    end_slot: u64,
+    attestor: Option<Pubkey>,
+) -> ProgramResult {
+    let CreateCampaignAccounts {
+        advertiser,
+        campaign: campaign_account,
+        vault: vault_account,
+        system_program: system_program_account,
+    } = CreateCampaignAccounts::parse(accounts)?;
+
+    let target_keywords = normalize_keywords(target_keywords);
+    validate_campaign_params(&target_keywords, rates.bid_per_impression, start_slot, end_slot)?;
+
+    let (expected_key, bump) = Campaign::find_address(program_id, advertiser.key, campaign_id);
+    accounts::expect_address(campaign_account, &expected_key, "campaign")?;
+    if !campaign_account.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    let (expected_vault, vault_bump) = Campaign::find_vault_address(program_id, campaign_account.key);
+    accounts::expect_address(vault_account, &expected_vault, "vault")?;
+
+    create_pda_account(
+        advertiser,
+        campaign_account,
+        Campaign::LEN,
+        program_id,
+        system_program_account,
+        &[
+            CAMPAIGN_SEED,
+            advertiser.key.as_ref(),
+            &campaign_id.to_le_bytes(),
+            &[bump],
+        ],
+    )?;
+
+    // Kasa veri taşımaz; yalnızca kira muafiyeti kadar lamport ile açılır
+    create_pda_account(
+        advertiser,
+        vault_account,
+        0,
+        program_id,
+        system_program_account,
+        &[VAULT_SEED, campaign_account.key.as_ref(), &[vault_bump]],
+    )?;
+
+    let campaign = Campaign {
+        is_initialized: true,
+        advertiser: *advertiser.key,
+        campaign_id,
+        bump,
+        status: CampaignStatus::Active,
+        target_keywords,
+        budget,
+        bid_per_impression: rates.bid_per_impression,
+        bid_per_click: rates.bid_per_click,
+        reward_per_dwell_second: rates.reward_per_dwell_second,
+        start_slot,
+        end_slot,
+        vault_bump,
+        deposited: 0,
+        spent: 0,
+        attestor: attestor.unwrap_or(*advertiser.key),
+    };
+    campaign.pack(&mut campaign_account.data.borrow_mut())?;
+
+    msg!("Campaign {} created for advertiser {}", campaign_id, advertiser.key);
+    Ok(())
+}
+
+/// Kampanya verisini okur ve imzalayanın kampanyanın sahibi olduğunu doğrular.
+fn load_advertiser_campaign(
+    advertiser: &AccountInfo,
+    campaign_account: &AccountInfo,
+) -> Result<Campaign, ProgramError> {
+    let campaign = Campaign::unpack(&campaign_account.data.borrow())?;
+    if campaign.advertiser != *advertiser.key {
+        return Err(SolfheError::Unauthorized.into());
     }
+    Ok(campaign)
+}
+
+fn update_campaign(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    target_keywords: Option<Vec<String>>,
+    budget: Option<u64>,
+    bid_per_impression: Option<u64>,
+    bid_per_click: Option<u64>,
+    reward_per_dwell_second: Option<u64>,
+    start_slot: Option<u64>,
+    end_slot: Option<u64>,
+    attestor: Option<Pubkey>,
+) -> ProgramResult {
+    let accounts = CampaignAuthorityAccounts::parse(program_id, accounts)?;
+    let campaign_account = accounts.campaign;
+    let mut campaign = load_advertiser_campaign(accounts.advertiser, campaign_account)?;
+
+    if let Some(target_keywords) = target_keywords {
+        campaign.target_keywords = normalize_keywords(target_keywords);
+    }
+    campaign.budget = budget.unwrap_or(campaign.budget);
+    campaign.bid_per_impression = bid_per_impression.unwrap_or(campaign.bid_per_impression);
+    campaign.bid_per_click = bid_per_click.unwrap_or(campaign.bid_per_click);
+    campaign.reward_per_dwell_second =
+        reward_per_dwell_second.unwrap_or(campaign.reward_per_dwell_second);
+    campaign.start_slot = start_slot.unwrap_or(campaign.start_slot);
+    campaign.end_slot = end_slot.unwrap_or(campaign.end_slot);
+    campaign.attestor = attestor.unwrap_or(campaign.attestor);
+    validate_campaign_params(
+        &campaign.target_keywords,
+        campaign.bid_per_impression,
+        campaign.start_slot,
+        campaign.end_slot,
+    )?;
+    if campaign.budget < campaign.spent {
+        return Err(SolfheError::InvalidCampaignParams.into());
+    }
+
+    campaign.pack(&mut campaign_account.data.borrow_mut())?;
+    msg!("Campaign {} updated", campaign.campaign_id);
     Ok(())
 }
 
-fn reward_user(accounts: &[AccountInfo], engagement_data: &[u8]) -> ProgramResult {
+fn pause_campaign(program_id: &Pubkey, accounts: &[AccountInfo], paused: bool) -> ProgramResult {
+    let accounts = CampaignAuthorityAccounts::parse(program_id, accounts)?;
+    let campaign_account = accounts.campaign;
+    let mut campaign = load_advertiser_campaign(accounts.advertiser, campaign_account)?;
+
+    campaign.status = if paused { CampaignStatus::Paused } else { CampaignStatus::Active };
+    campaign.pack(&mut campaign_account.data.borrow_mut())?;
+    msg!("Campaign {} is now {:?}", campaign.campaign_id, campaign.status);
+    Ok(())
+}
+
+fn close_campaign(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let CloseCampaignAccounts {
+        advertiser,
+        campaign: campaign_account,
+        vault: vault_account,
+    } = CloseCampaignAccounts::parse(program_id, accounts)?;
+    let campaign = load_advertiser_campaign(advertiser, campaign_account)?;
+
+    // Harcanmayan bütçe ve iki hesabın kirası reklam verene döner
+    let refund = vault_account
+        .lamports()
+        .checked_add(campaign_account.lamports())
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    close_account(vault_account, advertiser)?;
+    close_account(campaign_account, advertiser)?;
+
+    msg!(
+        "Campaign {} closed, refunded {} lamports (spent {} of {} deposited)",
+        campaign.campaign_id,
+        refund,
+        campaign.spent,
+        campaign.deposited
+    );
+    Ok(())
+}
+
+/// Hesabın tüm lamport'larını `destination`'a aktarır, verisini sıfırlar ve
+/// hesabı sistem programına devreder; eski veri aynı işlemde yeniden okunamaz.
+fn close_account(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+    let balance = destination
+        .lamports()
+        .checked_add(account.lamports())
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **destination.try_borrow_mut_lamports()? = balance;
+    **account.try_borrow_mut_lamports()? = 0;
+    account.data.borrow_mut().fill(0);
+    account.assign(&system_program::id());
+    Ok(())
+}
+
+/// PDA hesabını `owner`'a ait, `space` baytlık ve kira muafiyetli açar. Adrese
+/// önceden lamport gönderilmişse `create_account` başarısız olacağından eksik
+/// kira aktarılır, alan ayrılır ve hesap `owner`'a devredilir; böylece kimse
+/// öngörülebilir bir PDA'ya lamport göndererek oluşturmayı engelleyemez.
+fn create_pda_account<'a>(
+    payer: &AccountInfo<'a>,
+    account: &AccountInfo<'a>,
+    space: usize,
+    owner: &Pubkey,
+    system_program_account: &AccountInfo<'a>,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let rent = Rent::get()?.minimum_balance(space);
+    if account.lamports() == 0 {
+        return invoke_signed(
+            &system_instruction::create_account(payer.key, account.key, rent, space as u64, owner),
+            &[payer.clone(), account.clone(), system_program_account.clone()],
+            &[seeds],
+        );
+    }
+
+    let shortfall = rent.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        solana_program::program::invoke(
+            &system_instruction::transfer(payer.key, account.key, shortfall),
+            &[payer.clone(), account.clone(), system_program_account.clone()],
+        )?;
+    }
+    invoke_signed(
+        &system_instruction::allocate(account.key, space as u64),
+        &[account.clone(), system_program_account.clone()],
+        &[seeds],
+    )?;
+    invoke_signed(
+        &system_instruction::assign(account.key, owner),
+        &[account.clone(), system_program_account.clone()],
+        &[seeds],
+    )
+}
+
+fn reward_user(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    engagement_data: &[u8],
+) -> ProgramResult {
     // Kullanıcıya etkileşimi için ödül verme işlemi
     msg!("Rewarding user based on engagement data...");
-    
-    let reward_amount = calculate_reward(engagement_data)?;
-    let user_account = &accounts[0];
-    let system_program_account = &accounts[1];
-
-    // Solana sistem programı kullanarak ödülü transfer etme
-    let transfer_instruction = solana_program::system_instruction::transfer(
-        &system_program_account.key,
-        &user_account.key,
+
+    let RewardUserAccounts {
+        user: user_account,
+        attestor,
+        campaign: campaign_account,
+        vault: vault_account,
+        user_token_account,
+        reward_mint,
+        reward_authority,
+        system_program: system_program_account,
+        token_program,
+        associated_token_program,
+        claim_record,
+        reward_authority_bump: authority_bump,
+        claim_record_bump,
+    } = RewardUserAccounts::parse(program_id, accounts)?;
+
+    let mut campaign = Campaign::unpack(&campaign_account.data.borrow())?;
+    // Kullanıcı kendi raporuna kefil olamaz; reklam veren ya da oracle'ı da imzalar
+    if !campaign.accepts_attestor(attestor.key) {
+        return Err(SolfheError::MissingReportAttestation.into());
+    }
+    let current_slot = Clock::get()?.slot;
+    if !campaign.is_live(current_slot) {
+        return Err(SolfheError::CampaignNotLive.into());
+    }
+
+    let report = EngagementReport::decode(engagement_data)?;
+    let reward_amount = calculate_reward(&report, &campaign, current_slot)?;
+    if reward_amount > campaign.remaining_budget() {
+        return Err(SolfheError::CampaignExhausted.into());
+    }
+
+    // Aynı slot aralığı ikinci kez ödüllendirilmez
+    let rent = Rent::get()?;
+    if claim_record.data_is_empty() {
+        create_pda_account(
+            user_account,
+            claim_record,
+            ClaimRecord::LEN,
+            program_id,
+            system_program_account,
+            &[
+                CLAIM_SEED,
+                campaign_account.key.as_ref(),
+                user_account.key.as_ref(),
+                &[claim_record_bump],
+            ],
+        )?;
+    } else {
+        let record = ClaimRecord::unpack(&claim_record.data.borrow())?;
+        if report.start_slot <= record.last_claimed_slot {
+            return Err(SolfheError::ReportAlreadyClaimed.into());
+        }
+    }
+
+    // Ödül reklam verenin kasasından karşılanır; kasa kira muafiyetinin altına düşemez
+    let vault_balance = vault_account
+        .lamports()
+        .checked_sub(reward_amount)
+        .ok_or(SolfheError::CampaignExhausted)?;
+    if vault_balance < rent.minimum_balance(vault_account.data_len()) {
+        return Err(SolfheError::CampaignExhausted.into());
+    }
+    let authority_balance = reward_authority
+        .lamports()
+        .checked_add(reward_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    campaign.spent = campaign
+        .spent
+        .checked_add(reward_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // Kullanıcının Blinks hesabı yoksa oluştur (kirayı kullanıcı öder)
+    solana_program::program::invoke(
+        &spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+            user_account.key,
+            user_account.key,
+            reward_mint.key,
+            token_program.key,
+        ),
+        &[
+            user_account.clone(),
+            user_token_account.clone(),
+            user_account.clone(),
+            reward_mint.clone(),
+            system_program_account.clone(),
+            token_program.clone(),
+            associated_token_program.clone(),
+        ],
+    )?;
+
+    invoke_signed(
+        &spl_token::instruction::mint_to(
+            token_program.key,
+            reward_mint.key,
+            user_token_account.key,
+            reward_authority.key,
+            &[],
+            reward_amount,
+        )?,
+        &[
+            reward_mint.clone(),
+            user_token_account.clone(),
+            reward_authority.clone(),
+            token_program.clone(),
+        ],
+        &[&[REWARD_AUTHORITY_SEED, &[authority_bump]]],
+    )?;
+
+    // Harcanan lamport'lar basılan Blinks'in karşılığı olarak yetkili hesaba geçer
+    **vault_account.try_borrow_mut_lamports()? = vault_balance;
+    **reward_authority.try_borrow_mut_lamports()? = authority_balance;
+    campaign.pack(&mut campaign_account.data.borrow_mut())?;
+    ClaimRecord {
+        is_initialized: true,
+        last_claimed_slot: report.end_slot,
+    }
+    .pack(&mut claim_record.data.borrow_mut())?;
+
+    msg!(
+        "User rewarded with {} Blinks from campaign {}",
         reward_amount,
+        campaign.campaign_id
     );
+    Ok(())
+}
+
+fn initialize_reward_mint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    decimals: u8,
+) -> ProgramResult {
+    let InitializeRewardMintAccounts {
+        payer,
+        reward_mint,
+        reward_authority,
+        system_program: system_program_account,
+        token_program,
+        reward_mint_bump: mint_bump,
+        reward_authority_bump: authority_bump,
+    } = InitializeRewardMintAccounts::parse(program_id, accounts)?;
+
+    if !reward_mint.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    create_pda_account(
+        payer,
+        reward_mint,
+        spl_token::state::Mint::LEN,
+        token_program.key,
+        system_program_account,
+        &[REWARD_MINT_SEED, &[mint_bump]],
+    )?;
 
     solana_program::program::invoke(
-        &transfer_instruction,
-        &[system_program_account.clone(), user_account.clone()]
+        &spl_token::instruction::initialize_mint2(
+            token_program.key,
+            reward_mint.key,
+            reward_authority.key,
+            None,
+            decimals,
+        )?,
+        &[reward_mint.clone(), token_program.clone()],
     )?;
-    
-    msg!("User rewarded with {} lamports", reward_amount);
+
+    // Yetkili hesap program sahipli açılır; böylece `RedeemBlinks` karşılık
+    // lamport'larını doğrudan ödeyebilir.
+    create_pda_account(
+        payer,
+        reward_authority,
+        0,
+        program_id,
+        system_program_account,
+        &[REWARD_AUTHORITY_SEED, &[authority_bump]],
+    )?;
+
+    msg!("Blinks reward mint initialized: {}", reward_mint.key);
     Ok(())
 }
 
-fn calculate_reward(engagement_data: &[u8]) -> Result<u64, ProgramError> {
-    // Engagement verisini kullanarak ödül miktarını hesaplama
-    // Örneğin, etkileşim sayısına göre ödül miktarı belirlenebilir
-    let engagement_score = engagement_data.iter().map(|&x| x as u64).sum();
-    let reward_amount = engagement_score * 10; // Örnek ödül katsayısı
-    Ok(reward_amount)
+fn redeem_blinks(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let RedeemBlinksAccounts {
+        user,
+        user_token_account,
+        reward_mint,
+        reward_authority,
+        token_program,
+    } = RedeemBlinksAccounts::parse(program_id, accounts)?;
+    if amount == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Yetkili hesap kira muafiyetinin altına düşemez
+    let authority_balance = reward_authority
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(SolfheError::InsufficientRewardBacking)?;
+    if authority_balance < Rent::get()?.minimum_balance(reward_authority.data_len()) {
+        return Err(SolfheError::InsufficientRewardBacking.into());
+    }
+    let user_balance = user
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // Kullanıcının yeterli Blinks'i yoksa yakma başarısız olur
+    solana_program::program::invoke(
+        &spl_token::instruction::burn(
+            token_program.key,
+            user_token_account.key,
+            reward_mint.key,
+            user.key,
+            &[],
+            amount,
+        )?,
+        &[
+            user_token_account.clone(),
+            reward_mint.clone(),
+            user.clone(),
+            token_program.clone(),
+        ],
+    )?;
+
+    **reward_authority.try_borrow_mut_lamports()? = authority_balance;
+    **user.try_borrow_mut_lamports()? = user_balance;
+
+    msg!("Redeemed {} Blinks for {} lamports", amount, amount);
+    Ok(())
 }
 
-fn create_invoke_instruction(
-    payer: &Pubkey,
-    authority: &Pubkey,
-    input_compressed_accounts: &[CompressedAccount],
-    output_compressed_accounts: &[CompressedAccount],
-    merkle_context: &[MerkleContext],
-    merkle_tree_pubkeys: &[Pubkey],
-    root_indices: &[u16],
-    _new_address_params: &[()],
-    proof: Option<CompressedProof>,
-    compress_or_decompress_lamports: Option<u64>,
-    is_compress: bool,
-    decompression_recipient: Option<Pubkey>,
-    _sort: bool,
-) -> Instruction {
-    let mut accounts = vec![
-        AccountMeta::new(*payer, true),
-        AccountMeta::new_readonly(*authority, true),
-    ];
-
-    for pubkey in merkle_tree_pubkeys {
-        accounts.push(AccountMeta::new(*pubkey, false));
-    }
-
-    let mut data = Vec::new();
-    data.extend_from_slice(&(input_compressed_accounts.len() as u32).to_le_bytes());
-    data.extend_from_slice(&(output_compressed_accounts.len() as u32).to_le_bytes());
-    data.extend_from_slice(&(merkle_context.len() as u32).to_le_bytes());
-    data.extend_from_slice(&(root_indices.len() as u32).to_le_bytes());
-    
-    if let Some(proof) = proof {
-        data.push(1);
-        data.extend_from_slice(&proof.a);
-        data.extend_from_slice(&proof.b);
-        data.extend_from_slice(&proof.c);
-    } else {
-        data.push(0);
+fn fund_campaign(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let FundCampaignAccounts {
+        advertiser,
+        campaign: campaign_account,
+        vault: vault_account,
+        system_program: system_program_account,
+    } = FundCampaignAccounts::parse(program_id, accounts)?;
+    let mut campaign = load_advertiser_campaign(advertiser, campaign_account)?;
+
+    if amount == 0 {
+        return Err(SolfheError::InvalidCampaignParams.into());
     }
 
-    if let Some(lamports) = compress_or_decompress_lamports {
-        data.push(1);
-        data.extend_from_slice(&lamports.to_le_bytes());
-    } else {
-        data.push(0);
+    campaign.deposited = campaign
+        .deposited
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    solana_program::program::invoke(
+        &system_instruction::transfer(advertiser.key, vault_account.key, amount),
+        &[
+            advertiser.clone(),
+            vault_account.clone(),
+            system_program_account.clone(),
+        ],
+    )?;
+    campaign.pack(&mut campaign_account.data.borrow_mut())?;
+
+    msg!("Campaign {} funded with {} lamports", campaign.campaign_id, amount);
+    Ok(())
+}
+
+fn initialize_config(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts = InitializeConfigAccounts::parse(program_id, accounts)?;
+    // Kayıtlı ağaç listesi programın güven çapasıdır; yalnızca yükseltme yetkilisi yönetici olabilir
+    if accounts.upgrade_authority()? != Some(*accounts.admin.key) {
+        return Err(SolfheError::Unauthorized.into());
+    }
+    let InitializeConfigAccounts {
+        admin,
+        config: config_account,
+        system_program: system_program_account,
+        config_bump,
+        ..
+    } = accounts;
+
+    if !config_account.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
     }
 
-    data.push(is_compress as u8);
+    create_pda_account(
+        admin,
+        config_account,
+        ProgramConfig::LEN,
+        program_id,
+        system_program_account,
+        &[CONFIG_SEED, &[config_bump]],
+    )?;
 
-    if let Some(recipient) = decompression_recipient {
-        data.push(1);
-        data.extend_from_slice(&recipient.to_bytes());
-    } else {
-        data.push(0);
+    let config = ProgramConfig {
+        is_initialized: true,
+        admin: *admin.key,
+        bump: config_bump,
+        state_trees: Vec::new(),
+    };
+    config.pack(&mut config_account.data.borrow_mut())?;
+
+    msg!("Program config initialized with admin {}", admin.key);
+    Ok(())
+}
+
+/// Yapılandırmayı okur ve imzalayanın yönetici olduğunu doğrular.
+fn load_admin_config(admin: &AccountInfo, config_account: &AccountInfo) -> Result<ProgramConfig, ProgramError> {
+    let config = ProgramConfig::unpack(&config_account.data.borrow())?;
+    if config.admin != *admin.key {
+        return Err(SolfheError::Unauthorized.into());
+    }
+    Ok(config)
+}
+
+fn register_state_tree(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    merkle_tree: Pubkey,
+    nullifier_queue: Pubkey,
+) -> ProgramResult {
+    let accounts = ConfigAdminAccounts::parse(program_id, accounts)?;
+    let mut config = load_admin_config(accounts.admin, accounts.config)?;
+
+    if !config.is_registered(&merkle_tree, &nullifier_queue) {
+        if config.state_trees.len() >= MAX_REGISTERED_TREES {
+            return Err(SolfheError::StateTreeRegistryFull.into());
+        }
+        config.state_trees.push(RegisteredTree {
+            merkle_tree,
+            nullifier_queue,
+        });
     }
+    config.pack(&mut accounts.config.data.borrow_mut())?;
 
-    Instruction {
-        program_id: PROGRAM_ID_LIGHT_TOKEN,
-        accounts,
-        data,
+    msg!("State tree {} registered with queue {}", merkle_tree, nullifier_queue);
+    Ok(())
+}
+
+fn remove_state_tree(program_id: &Pubkey, accounts: &[AccountInfo], merkle_tree: Pubkey) -> ProgramResult {
+    let accounts = ConfigAdminAccounts::parse(program_id, accounts)?;
+    let mut config = load_admin_config(accounts.admin, accounts.config)?;
+
+    let before = config.state_trees.len();
+    config.state_trees.retain(|t| t.merkle_tree != merkle_tree);
+    if config.state_trees.len() == before {
+        return Err(SolfheError::UnregisteredStateTree.into());
     }
+    config.pack(&mut accounts.config.data.borrow_mut())?;
+
+    msg!("State tree {} removed", merkle_tree);
+    Ok(())
 }
 
 fn extract_keywords(url: &str) -> Vec<String> {
-    url.split(|c: char| !c.is_alphanumeric())
-        .filter(|s| !s.is_empty() && s.len() > 3)
-        .map(|s| s.to_lowercase())
+    tokenizer::tokenize_url(url)
+        .into_iter()
+        .filter(|s| s.chars().count() > 3)
         .collect()
 }
 
-fn hash_data(data: &str) -> [u8; 32] {
+fn hash_data(data: impl AsRef<[u8]>) -> [u8; 32] {
     let mut hasher = Sha256::new();
     hasher.update(data);
     hasher.finalize().into()
@@ -381,14 +1141,27 @@ fn hash_data(data: &str) -> [u8; 32] {
 // Off-chain istemci kodu
 #[cfg(not(target_os = "solana"))]
 pub mod client {
-    use solana_client::rpc_client::RpcClient;
+    use solana_client::{client_error::ClientError, rpc_client::RpcClient};
     use solana_sdk::{
         signature::{Keypair, Signer},
-        transaction::Transaction,
-        instruction::Instruction,
+        transaction::{Transaction, TransactionError},
+        instruction::{AccountMeta, Instruction, InstructionError},
     };
     use super::*;
 
+    pub use crate::error::SolfheError;
+
+    /// Başarısız bir işlemin özel hata kodunu `SolfheError`'a çevirir; hata
+    /// programdan gelmiyorsa `None` döner.
+    pub fn decode_error(error: &ClientError) -> Option<SolfheError> {
+        match error.get_transaction_error()? {
+            TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+                SolfheError::from_code(code)
+            },
+            _ => None,
+        }
+    }
+
     pub async fn run_solfhe_analyzer(client: &RpcClient, payer: &Keypair) -> Result<(), Box<dyn std::error::Error>> {
         let links = extract_links_from_chrome()?;
         
@@ -396,7 +1169,10 @@ pub mod client {
         let instruction = Instruction::new_with_borsh(
             *program_id(),
             &instruction_data,
-            vec![AccountMeta::new(payer.pubkey(), true)],
+            vec![
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(spl_memo::id(), false),
+            ],
         );
 
         let recent_blockhash = client.get_latest_blockhash()?;
@@ -407,7 +1183,14 @@ pub mod client {
             recent_blockhash,
         );
 
-        let signature = client.send_and_confirm_transaction(&transaction)?;
+        let signature = client
+            .send_and_confirm_transaction(&transaction)
+            .map_err(|e| -> Box<dyn std::error::Error> {
+                match decode_error(&e) {
+                    Some(reason) => Box::new(reason),
+                    None => Box::new(e),
+                }
+            })?;
         println!("Transaction sent: {}", signature);
 
         let transaction_data = client.get_transaction(&signature, UiTransactionEncoding::Json)?;
@@ -464,13 +1247,807 @@ mod tests {
         assert!(keywords.contains(&"page".to_string()));
         assert!(!keywords.contains(&"www".to_string()));  // 3 karakterden kısa
         assert!(!keywords.contains(&"com".to_string()));  // 3 karakterden kısa
+
+        // Uzunluk bayt değil karakter sayısıdır: "çay" 4 bayt ama 3 karakter
+        let keywords = extract_keywords("https://example.org/çay/kahve");
+        assert!(!keywords.contains(&"çay".to_string()));
+        assert!(keywords.contains(&"kahve".to_string()));
+    }
+
+    #[test]
+    fn test_tokenize_segment() {
+        use tokenizer::tokenize_segment;
+
+        assert_eq!(tokenize_segment("zk-compression_explained"), vec!["zk", "compression", "explained"]);
+        assert_eq!(tokenize_segment("getUserProfile"), vec!["get", "user", "profile"]);
+        assert_eq!(tokenize_segment("HTMLParser"), vec!["html", "parser"]);
+        assert_eq!(tokenize_segment("web3.js"), vec!["web3"]);
+        assert_eq!(tokenize_segment("popup.html"), vec!["popup"]);
+        assert_eq!(tokenize_segment("release.notes.v2"), vec!["release", "notes", "v2"]);
+
+        // Kimlikler ve hash'ler atılır
+        assert!(tokenize_segment("dbgnhckhnppddckangcjbkjnlddbjkna").is_empty());
+        assert!(tokenize_segment("123456").is_empty());
+        assert!(tokenize_segment("a3f9c2e1").is_empty());
+        assert!(tokenize_segment("d41d8cd98f00b204e9800998ecf8427e").is_empty());
+        assert!(tokenize_segment("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v").is_empty());
+        assert_eq!(
+            tokenize_segment("post-3f2504e0-4f89-11d3-9a0c-0305e82c3301-solana"),
+            vec!["post", "solana"]
+        );
+        // Uzun ama okunabilir kelimeler kalır
+        assert_eq!(tokenize_segment("SolanaBreakpointConference2024"), vec!["solana", "breakpoint", "conference2024"]);
+        assert_eq!(tokenize_segment("decentralization"), vec!["decentralization"]);
+    }
+
+    #[test]
+    fn test_extract_keywords_drops_ids() {
+        let keywords = extract_keywords(
+            "chrome-extension://dbgnhckhnppddckangcjbkjnlddbjkna/popup.html?tab=stakingRewards",
+        );
+        assert_eq!(keywords, vec!["chrome", "extension", "popup", "staking", "rewards"]);
+    }
+
+    fn test_campaign(keywords: &[&str], bid_per_impression: u64) -> Campaign {
+        Campaign {
+            is_initialized: true,
+            advertiser: Pubkey::new_unique(),
+            campaign_id: 1,
+            bump: 255,
+            status: CampaignStatus::Active,
+            target_keywords: keywords.iter().map(|k| k.to_string()).collect(),
+            budget: 1_000,
+            bid_per_impression,
+            bid_per_click: 20,
+            reward_per_dwell_second: 1,
+            start_slot: 10,
+            end_slot: 100,
+            vault_bump: 254,
+            deposited: 1_000,
+            spent: 0,
+            attestor: Pubkey::new_unique(),
+        }
+    }
+
+    #[test]
+    fn test_campaign_round_trip() {
+        let campaign = test_campaign(&["solana", "defi"], 5);
+        let mut data = vec![0u8; Campaign::LEN];
+        campaign.pack(&mut data).unwrap();
+        assert_eq!(Campaign::unpack(&data).unwrap(), campaign);
+        assert!(Campaign::unpack(&[0u8; Campaign::LEN]).is_err());
+    }
+
+    #[test]
+    fn test_remaining_budget() {
+        let mut campaign = test_campaign(&["solana"], 5);
+        campaign.deposited = 400;
+        campaign.spent = 150;
+        // Yatırılmayan bütçe harcanamaz
+        assert_eq!(campaign.remaining_budget(), 250);
+        campaign.deposited = 5_000;
+        assert_eq!(campaign.remaining_budget(), 850);
+        campaign.spent = 1_000;
+        assert_eq!(campaign.remaining_budget(), 0);
+    }
+
+    #[test]
+    fn test_select_campaign() {
+        let solana = (Pubkey::new_unique(), test_campaign(&["solana"], 5));
+        let defi = (Pubkey::new_unique(), test_campaign(&["defi", "aave"], 2));
+        let mut paused = (Pubkey::new_unique(), test_campaign(&["solana", "defi"], 50));
+        paused.1.status = CampaignStatus::Paused;
+        let campaigns = vec![solana.clone(), defi.clone(), paused];
+
+        let profile = profile_keywords(
+            br#"{"top_words":[["defi",3],["solana",2],["aave",1]],"total_links_analyzed":5}"#,
+        );
+        assert_eq!(select_campaign(&campaigns, &profile, 50), Some((defi.0, 4)));
+        // Kampanya penceresi dışında hiçbir reklam sunulmaz
+        assert_eq!(select_campaign(&campaigns, &profile, 101), None);
+
+        let tie = vec![(Pubkey::new_unique(), test_campaign(&["solana"], 1)), solana.clone()];
+        let profile = vec![("solana".to_string(), 2)];
+        assert_eq!(select_campaign(&tie, &profile, 50), Some((solana.0, 2)));
+    }
+
+    fn test_compressed_account(data: &str) -> CompressedAccount {
+        CompressedAccount {
+            owner: Pubkey::new_unique(),
+            lamports: 0,
+            address: None,
+            data: Some(CompressedAccountData {
+                discriminator: [7; 8],
+                data: data.as_bytes().to_vec(),
+                data_hash: hash_data(data),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_invoke_cpi_instruction_round_trip() {
+        use light::{InstructionDataInvokeCpi, NewAddressParams, INVOKE_CPI_DISCRIMINATOR};
+        use light_sdk::{
+            constants::PROGRAM_ID_LIGHT_SYSTEM,
+            merkle_context::{MerkleContext, QueueIndex},
+            proof::CompressedProof,
+        };
+
+        let payer = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        let tree = Pubkey::new_unique();
+        let queue = Pubkey::new_unique();
+        let other_tree = Pubkey::new_unique();
+        let address_tree = Pubkey::new_unique();
+        let address_queue = Pubkey::new_unique();
+
+        let input = test_compressed_account("input");
+        let context = MerkleContext {
+            merkle_tree_pubkey: tree,
+            nullifier_queue_pubkey: queue,
+            leaf_index: 42,
+            queue_index: Some(QueueIndex { queue_id: 1, index: 3 }),
+        };
+        let outputs = vec![
+            test_compressed_account("second tree"),
+            test_compressed_account("first tree"),
+        ];
+        let proof = CompressedProof { a: [1; 32], b: [2; 64], c: [3; 32] };
+        let new_address = NewAddressParams {
+            seed: [9; 32],
+            address_queue_pubkey: address_queue,
+            address_merkle_tree_pubkey: address_tree,
+            address_merkle_tree_root_index: 11,
+        };
+
+        let instruction = create_invoke_cpi_instruction(
+            &payer,
+            &program_id,
+            std::slice::from_ref(&input),
+            &[context],
+            &[5],
+            false,
+            &outputs,
+            &[other_tree, tree],
+            &[new_address],
+            Some(proof),
+            true,
+        )
+        .unwrap();
+
+        assert_eq!(instruction.program_id, PROGRAM_ID_LIGHT_SYSTEM);
+        assert_eq!(instruction.data[..8], INVOKE_CPI_DISCRIMINATOR);
+
+        // Kalan hesaplar sabit 11 Light hesabından sonra gelir
+        let remaining: Vec<Pubkey> = instruction.accounts[11..].iter().map(|m| m.pubkey).collect();
+        assert_eq!(remaining, vec![tree, queue, other_tree, address_queue, address_tree]);
+        assert_eq!(instruction.accounts[6].pubkey, program_id);
+
+        let decoded = InstructionDataInvokeCpi::decode(&instruction.data).unwrap();
+        assert_eq!(decoded.proof, Some(proof));
+        assert_eq!(decoded.compress_or_decompress_lamports, None);
+        assert!(!decoded.is_compress);
+        assert_eq!(decoded.cpi_context, None);
+
+        let packed_input = &decoded.input_compressed_accounts_with_merkle_context[0];
+        assert_eq!(packed_input.compressed_account, input);
+        assert_eq!(packed_input.root_index, 5);
+        assert!(!packed_input.read_only);
+        assert_eq!(packed_input.merkle_context.merkle_tree_pubkey_index, 0);
+        assert_eq!(packed_input.merkle_context.nullifier_queue_pubkey_index, 1);
+        assert_eq!(packed_input.merkle_context.leaf_index, 42);
+        assert_eq!(packed_input.merkle_context.queue_index, context.queue_index);
+
+        // Sıralama sonrası `tree` (indeks 0) çıktısı öne geçer
+        let output_trees: Vec<u8> =
+            decoded.output_compressed_accounts.iter().map(|o| o.merkle_tree_index).collect();
+        assert_eq!(output_trees, vec![0, 2]);
+        assert_eq!(decoded.output_compressed_accounts[0].compressed_account, outputs[1]);
+        assert_eq!(decoded.output_compressed_accounts[1].compressed_account, outputs[0]);
+
+        let packed_address = decoded.new_address_params[0];
+        assert_eq!(packed_address.seed, [9; 32]);
+        assert_eq!(packed_address.address_queue_account_index, 3);
+        assert_eq!(packed_address.address_merkle_tree_account_index, 4);
+        assert_eq!(packed_address.address_merkle_tree_root_index, 11);
+
+        assert_eq!(decoded.encode().unwrap(), instruction.data);
+    }
+
+    #[test]
+    fn test_inclusion_check_instruction() {
+        use light_sdk::{merkle_context::MerkleContext, proof::CompressedProof};
+
+        let payer = Pubkey::new_unique();
+        let account = test_compressed_account("profile");
+        let context = MerkleContext {
+            merkle_tree_pubkey: Pubkey::new_unique(),
+            nullifier_queue_pubkey: Pubkey::new_unique(),
+            leaf_index: 7,
+            queue_index: None,
+        };
+        let proof = CompressedProof { a: [4; 32], b: [5; 64], c: [6; 32] };
+
+        let program_id = Pubkey::new_unique();
+        let instruction =
+            light::create_inclusion_check_instruction(&payer, &program_id, &account, &context, 3, proof)
+                .unwrap();
+        assert_eq!(instruction.data[..8], light::INVOKE_CPI_DISCRIMINATOR);
+        let decoded = light::InstructionDataInvokeCpi::decode(&instruction.data).unwrap();
+
+        // Hesap salt okunur girdidir: harcanmaz ve yeniden yazılmaz
+        let input = &decoded.input_compressed_accounts_with_merkle_context[0];
+        assert!(input.read_only);
+        assert!(decoded.output_compressed_accounts.is_empty());
+        assert_eq!(input.compressed_account, account);
+        assert_eq!(input.merkle_context.leaf_index, 7);
+        assert_eq!(input.root_index, 3);
+        assert_eq!(decoded.proof, Some(proof));
+        assert_eq!(decoded.cpi_context, None);
+
+        // Yetkili kullanıcı değil, programın CPI yetkili PDA'sıdır
+        let (cpi_authority, _) = light::find_cpi_authority_address(&program_id);
+        assert!(instruction.accounts[0].pubkey == payer && instruction.accounts[0].is_signer);
+        assert_eq!(instruction.accounts[1].pubkey, cpi_authority);
+        assert!(instruction.accounts[1].is_signer && !instruction.accounts[1].is_writable);
+        assert_eq!(instruction.accounts[6].pubkey, program_id);
+
+        let remaining: Vec<Pubkey> = instruction.accounts[11..].iter().map(|m| m.pubkey).collect();
+        assert_eq!(remaining, vec![context.merkle_tree_pubkey, context.nullifier_queue_pubkey]);
+        assert_eq!(decoded.encode().unwrap(), instruction.data);
+    }
+
+    #[test]
+    fn test_invoke_cpi_instruction_rejects_bad_input() {
+        let tree = Pubkey::new_unique();
+        let outputs = vec![test_compressed_account("a"), test_compressed_account("b")];
+
+        // Sıralama kapalıyken çıktılar verildiği sırada kalır
+        let instruction = create_invoke_cpi_instruction(
+            &tree, &tree, &[], &[], &[], false, &outputs, &[Pubkey::new_unique(), tree], &[], None, false,
+        )
+        .unwrap();
+        let decoded = light::InstructionDataInvokeCpi::decode(&instruction.data).unwrap();
+        assert_eq!(decoded.output_compressed_accounts[0].compressed_account, outputs[0]);
+        assert_eq!(decoded.output_compressed_accounts[0].merkle_tree_index, 0);
+
+        // Her çıktının bir ağacı olmalı
+        assert_eq!(
+            create_invoke_cpi_instruction(&tree, &tree, &[], &[], &[], false, &outputs, &[tree], &[], None, true),
+            Err(ProgramError::InvalidArgument)
+        );
+        // Yanlış ayırıcı reddedilir
+        let mut data = instruction.data.clone();
+        data[0] ^= 1;
+        assert!(light::InstructionDataInvokeCpi::decode(&data).is_err());
+        assert!(light::InstructionDataInvokeCpi::decode(&[]).is_err());
+    }
+
+    #[test]
+    fn test_payload_discriminators() {
+        for kind in PayloadKind::ALL {
+            let hash = hash_data(format!("solfhe:{}", kind.name()));
+            assert_eq!(kind.discriminator(), hash[..8]);
+            assert_eq!(PayloadKind::from_discriminator(&kind.discriminator()), Some(kind));
+            assert!(kind.expect(&kind.discriminator()).is_ok());
+        }
+        assert_eq!(PayloadKind::from_discriminator(&[0; 8]), None);
+        assert_eq!(
+            PayloadKind::UserProfile.expect(&[0; 8]),
+            Err(SolfheError::InvalidDiscriminator.into())
+        );
+        assert_eq!(
+            PayloadKind::UserProfile.expect(&PayloadKind::AnalysisResult.discriminator()),
+            Err(SolfheError::InvalidDiscriminator.into())
+        );
+    }
+
+    #[test]
+    fn test_registered_state_trees() {
+        let tree = RegisteredTree {
+            merkle_tree: Pubkey::new_unique(),
+            nullifier_queue: Pubkey::new_unique(),
+        };
+        let config = ProgramConfig {
+            is_initialized: true,
+            admin: Pubkey::new_unique(),
+            bump: 255,
+            state_trees: vec![tree; MAX_REGISTERED_TREES],
+        };
+        let mut data = vec![0u8; ProgramConfig::LEN];
+        config.pack(&mut data).unwrap();
+        let config = ProgramConfig::unpack(&data).unwrap();
+
+        assert!(config.is_registered(&tree.merkle_tree, &tree.nullifier_queue));
+        // Ağaç başka bir kuyrukla eşleştirilemez
+        assert!(!config.is_registered(&tree.merkle_tree, &Pubkey::new_unique()));
+        assert!(!config.is_registered(&Pubkey::new_unique(), &tree.nullifier_queue));
+    }
+
+    #[test]
+    fn test_account_parsing_errors() {
+        let program_id = Pubkey::new_unique();
+        let advertiser_key = Pubkey::new_unique();
+        let campaign_key = Pubkey::new_unique();
+        let system_owner = system_program::id();
+        let (mut advertiser_lamports, mut campaign_lamports) = (0, 0);
+        let (mut advertiser_data, mut campaign_data) = (vec![], vec![0u8; Campaign::LEN]);
+
+        let advertiser = AccountInfo::new(
+            &advertiser_key,
+            false,
+            true,
+            &mut advertiser_lamports,
+            &mut advertiser_data,
+            &system_owner,
+            false,
+            0,
+        );
+        let campaign = AccountInfo::new(
+            &campaign_key,
+            false,
+            true,
+            &mut campaign_lamports,
+            &mut campaign_data,
+            &system_owner,
+            false,
+            0,
+        );
+        let code = |e: SolfheError| ProgramError::Custom(e as u32);
+
+        let missing = CampaignAuthorityAccounts::parse(&program_id, &[]);
+        assert_eq!(missing.err(), Some(code(SolfheError::MissingAccount)));
+
+        let accounts = vec![advertiser.clone(), campaign.clone()];
+        let not_signer = CampaignAuthorityAccounts::parse(&program_id, &accounts);
+        assert_eq!(not_signer.err(), Some(code(SolfheError::AccountNotSigner)));
+
+        let mut signed = advertiser.clone();
+        signed.is_signer = true;
+        let accounts = vec![signed.clone(), campaign.clone()];
+        let wrong_owner = CampaignAuthorityAccounts::parse(&program_id, &accounts);
+        assert_eq!(wrong_owner.err(), Some(code(SolfheError::IncorrectAccountOwner)));
+
+        let mut owned = campaign.clone();
+        owned.owner = &program_id;
+        owned.is_writable = false;
+        let accounts = vec![signed.clone(), owned.clone()];
+        let read_only = CampaignAuthorityAccounts::parse(&program_id, &accounts);
+        assert_eq!(read_only.err(), Some(code(SolfheError::AccountNotWritable)));
+
+        owned.is_writable = true;
+        let accounts = vec![signed, owned];
+        assert!(CampaignAuthorityAccounts::parse(&program_id, &accounts).is_ok());
+
+        // Memo programı yerine başka bir hesap verilirse
+        let accounts = vec![accounts[0].clone(), campaign];
+        let not_memo = MemoAccounts::parse(&accounts);
+        assert_eq!(not_memo.err(), Some(code(SolfheError::IncorrectProgramAccount)));
+    }
+
+    #[test]
+    fn test_close_campaign_refunds_rent() {
+        let program_id = Pubkey::new_unique();
+        let advertiser_key = Pubkey::new_unique();
+        let (campaign_key, _) = Campaign::find_address(&program_id, &advertiser_key, 1);
+        let (vault_key, _) = Campaign::find_vault_address(&program_id, &campaign_key);
+        let system_owner = system_program::id();
+        // `assign` sahibi yerinde değiştirir, bu yüzden her hesabın kendi kopyası var
+        let (campaign_owner, vault_owner) = (program_id, program_id);
+
+        let mut campaign = test_campaign(&["solana"], 5);
+        campaign.advertiser = advertiser_key;
+        let mut campaign_data = vec![0u8; Campaign::LEN];
+        campaign.pack(&mut campaign_data).unwrap();
+        let mut vault_data = vec![];
+        let mut advertiser_data = vec![];
+        let (mut advertiser_lamports, mut campaign_lamports, mut vault_lamports) =
+            (1_000, 3_563_520, 890_880 + 400);
+
+        let accounts = vec![
+            AccountInfo::new(
+                &advertiser_key,
+                true,
+                true,
+                &mut advertiser_lamports,
+                &mut advertiser_data,
+                &system_owner,
+                false,
+                0,
+            ),
+            AccountInfo::new(
+                &campaign_key,
+                false,
+                true,
+                &mut campaign_lamports,
+                &mut campaign_data,
+                &campaign_owner,
+                false,
+                0,
+            ),
+            AccountInfo::new(
+                &vault_key,
+                false,
+                true,
+                &mut vault_lamports,
+                &mut vault_data,
+                &vault_owner,
+                false,
+                0,
+            ),
+        ];
+
+        close_campaign(&program_id, &accounts).unwrap();
+
+        // Kira ve harcanmayan 400 lamport reklam verene döner
+        assert_eq!(accounts[0].lamports(), 1_000 + 3_563_520 + 890_880 + 400);
+        for closed in &accounts[1..] {
+            assert_eq!(closed.lamports(), 0);
+            assert_eq!(closed.owner, &system_owner);
+            assert!(closed.data.borrow().iter().all(|&b| b == 0));
+        }
+        assert!(Campaign::unpack(&accounts[1].data.borrow()).is_err());
+    }
+
+    fn test_report() -> EngagementReport {
+        EngagementReport {
+            version: engagement::ENGAGEMENT_REPORT_VERSION,
+            campaign_id: 1,
+            impressions: 3,
+            clicks: 1,
+            dwell_time_secs: 12,
+            start_slot: 20,
+            end_slot: 60,
+        }
     }
 
     #[test]
     fn test_calculate_reward() {
-        let engagement_data = vec![5, 10, 15];
-        let reward = calculate_reward(&engagement_data).unwrap();
-        assert_eq!(reward, 300); // Engagement skoru 30, katsayı 10
+        let campaign = test_campaign(&["solana"], 5);
+        let current_slot = 80;
+
+        let cases: Vec<(&str, EngagementReport, Result<u64, ProgramError>)> = vec![
+            // 3 * 5 + 1 * 20 + 12 * 1
+            ("valid report", test_report(), Ok(47)),
+            (
+                "impressions only",
+                EngagementReport { clicks: 0, dwell_time_secs: 0, ..test_report() },
+                Ok(15),
+            ),
+            (
+                "unknown version",
+                EngagementReport { version: 2, ..test_report() },
+                Err(SolfheError::UnsupportedReportVersion.into()),
+            ),
+            (
+                "other campaign",
+                EngagementReport { campaign_id: 7, ..test_report() },
+                Err(SolfheError::InvalidEngagementReport.into()),
+            ),
+            (
+                "no impressions",
+                EngagementReport { impressions: 0, clicks: 0, ..test_report() },
+                Err(SolfheError::InvalidEngagementReport.into()),
+            ),
+            (
+                "too many impressions",
+                EngagementReport {
+                    impressions: engagement::MAX_IMPRESSIONS_PER_REPORT + 1,
+                    ..test_report()
+                },
+                Err(SolfheError::InvalidEngagementReport.into()),
+            ),
+            (
+                "more clicks than impressions",
+                EngagementReport { clicks: 4, ..test_report() },
+                Err(SolfheError::InvalidEngagementReport.into()),
+            ),
+            (
+                "dwell longer than the slot range",
+                EngagementReport { dwell_time_secs: 18, ..test_report() },
+                Err(SolfheError::InvalidEngagementReport.into()),
+            ),
+            (
+                "reversed slot range",
+                EngagementReport { start_slot: 61, ..test_report() },
+                Err(SolfheError::InvalidEngagementReport.into()),
+            ),
+            (
+                "range in the future",
+                EngagementReport { end_slot: 81, ..test_report() },
+                Err(SolfheError::InvalidEngagementReport.into()),
+            ),
+            (
+                "range before the campaign",
+                EngagementReport { start_slot: 5, ..test_report() },
+                Err(SolfheError::InvalidEngagementReport.into()),
+            ),
+            (
+                "more impressions than slots",
+                EngagementReport { start_slot: 60, clicks: 0, dwell_time_secs: 0, ..test_report() },
+                Err(SolfheError::InvalidEngagementReport.into()),
+            ),
+            (
+                "one impression per slot",
+                EngagementReport { start_slot: 58, clicks: 0, dwell_time_secs: 0, ..test_report() },
+                Ok(15),
+            ),
+        ];
+
+        for (name, report, expected) in cases {
+            assert_eq!(calculate_reward(&report, &campaign, current_slot), expected, "{}", name);
+        }
+
+        let mut expensive = test_campaign(&["solana"], u64::MAX);
+        expensive.bid_per_click = 0;
+        assert_eq!(
+            calculate_reward(&test_report(), &expensive, current_slot),
+            Err(ProgramError::ArithmeticOverflow)
+        );
     }
-}
 
+    #[test]
+    fn test_report_attestors() {
+        let campaign = test_campaign(&["solana"], 5);
+        assert!(campaign.accepts_attestor(&campaign.advertiser));
+        assert!(campaign.accepts_attestor(&campaign.attestor));
+        assert!(!campaign.accepts_attestor(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_decode_engagement_report() {
+        let report = test_report();
+        let data = report.try_to_vec().unwrap();
+        assert_eq!(EngagementReport::decode(&data).unwrap(), report);
+
+        // Eski format: ham bayt toplamı artık ödül üretmez
+        assert_eq!(
+            EngagementReport::decode(&[0xFF; 10]),
+            Err(SolfheError::UnsupportedReportVersion.into())
+        );
+        let mut trailing = data.clone();
+        trailing.push(0);
+        assert_eq!(
+            EngagementReport::decode(&trailing),
+            Err(SolfheError::InvalidEngagementReport.into())
+        );
+    }
+
+    #[test]
+    fn test_error_codes_round_trip() {
+        for code in 0..20 {
+            let error = SolfheError::from_code(code).unwrap();
+            assert_eq!(ProgramError::from(error), ProgramError::Custom(code));
+        }
+        assert_eq!(SolfheError::from_code(12), Some(SolfheError::ReportAlreadyClaimed));
+        assert_eq!(SolfheError::from_code(20), None);
+    }
+
+    /// Testlerde `Rent::get` varsayılan kirayı döner; CPI'lar (ör. SPL token
+    /// çağrıları) çalıştırılmadan başarılı sayılır ve `INVOKED`'a kaydedilir.
+    struct TestSysvars;
+
+    thread_local! {
+        static INVOKED: std::cell::RefCell<Vec<solana_program::instruction::Instruction>> =
+            const { std::cell::RefCell::new(Vec::new()) };
+    }
+
+    impl solana_program::program_stubs::SyscallStubs for TestSysvars {
+        fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+            unsafe { *(var_addr as *mut Rent) = Rent::default() };
+            solana_program::entrypoint::SUCCESS
+        }
+
+        fn sol_invoke_signed(
+            &self,
+            instruction: &solana_program::instruction::Instruction,
+            _account_infos: &[AccountInfo],
+            _signers_seeds: &[&[&[u8]]],
+        ) -> ProgramResult {
+            INVOKED.with(|invoked| invoked.borrow_mut().push(instruction.clone()));
+            Ok(())
+        }
+    }
+
+    fn stub_sysvars() {
+        static STUBS: std::sync::Once = std::sync::Once::new();
+        STUBS.call_once(|| {
+            solana_program::program_stubs::set_syscall_stubs(Box::new(TestSysvars));
+        });
+    }
+
+    #[test]
+    fn test_redeem_blinks() {
+        stub_sysvars();
+        let program_id = Pubkey::new_unique();
+        let user_key = Pubkey::new_unique();
+        let (mint_key, _) = state::find_reward_mint_address(&program_id);
+        let (authority_key, _) = state::find_reward_authority_address(&program_id);
+        let token_account_key =
+            spl_associated_token_account::get_associated_token_address(&user_key, &mint_key);
+        let (system_owner, token_owner, loader) =
+            (system_program::id(), spl_token::id(), solana_program::bpf_loader::id());
+        let token_program_key = spl_token::id();
+        let rent = Rent::default().minimum_balance(0);
+
+        let (mut user_lamports, mut token_account_lamports, mut mint_lamports, mut authority_lamports) =
+            (0, 0, 0, rent + 500);
+        let mut token_program_lamports = 0;
+        let (mut user_data, mut token_account_data, mut mint_data, mut authority_data) =
+            (vec![], vec![], vec![], vec![]);
+        let mut token_program_data = vec![];
+        let accounts = vec![
+            AccountInfo::new(&user_key, true, true, &mut user_lamports, &mut user_data, &system_owner, false, 0),
+            AccountInfo::new(
+                &token_account_key,
+                false,
+                true,
+                &mut token_account_lamports,
+                &mut token_account_data,
+                &token_owner,
+                false,
+                0,
+            ),
+            AccountInfo::new(&mint_key, false, true, &mut mint_lamports, &mut mint_data, &token_owner, false, 0),
+            AccountInfo::new(
+                &authority_key,
+                false,
+                true,
+                &mut authority_lamports,
+                &mut authority_data,
+                &program_id,
+                false,
+                0,
+            ),
+            AccountInfo::new(
+                &token_program_key,
+                false,
+                false,
+                &mut token_program_lamports,
+                &mut token_program_data,
+                &loader,
+                true,
+                0,
+            ),
+        ];
+
+        redeem_blinks(&program_id, &accounts, 300).unwrap();
+        assert_eq!(accounts[0].lamports(), 300);
+        assert_eq!(accounts[3].lamports(), rent + 200);
+
+        // Karşılık, yetkili hesabın kirasına dokunmadan ödenebilecek kadardır
+        assert_eq!(
+            redeem_blinks(&program_id, &accounts, 201),
+            Err(SolfheError::InsufficientRewardBacking.into())
+        );
+        assert_eq!(redeem_blinks(&program_id, &accounts, 0), Err(ProgramError::InvalidArgument));
+        redeem_blinks(&program_id, &accounts, 200).unwrap();
+        assert_eq!(accounts[3].lamports(), rent);
+    }
+    #[test]
+    fn test_initialize_config_requires_upgrade_authority() {
+        let program_id = Pubkey::new_unique();
+        let authority_key = Pubkey::new_unique();
+        let (config_key, _) = ProgramConfig::find_address(&program_id);
+        let loader = solana_program::bpf_loader_upgradeable::id();
+        let (program_data_key, _) = Pubkey::find_program_address(&[program_id.as_ref()], &loader);
+        let (system_owner, system_program_key) = (system_program::id(), system_program::id());
+
+        // `UpgradeableLoaderState::ProgramData { slot: 7, upgrade_authority_address }`
+        let program_data = |authority: Option<Pubkey>| {
+            let mut data = 3u32.to_le_bytes().to_vec();
+            data.extend_from_slice(&7u64.to_le_bytes());
+            data.push(authority.is_some() as u8);
+            data.extend_from_slice(authority.unwrap_or_default().as_ref());
+            data
+        };
+
+        for (admin_key, authority) in [
+            (authority_key, Some(authority_key)),
+            (Pubkey::new_unique(), Some(authority_key)),
+            // Değiştirilemez programın yükseltme yetkilisi yoktur
+            (authority_key, None),
+        ] {
+            let mut data = program_data(authority);
+            let (mut admin_lamports, mut config_lamports, mut program_data_lamports, mut system_lamports) =
+                (0, 0, 0, 0);
+            let (mut admin_data, mut config_data, mut system_data) = (vec![], vec![], vec![]);
+            let accounts = vec![
+                AccountInfo::new(&admin_key, true, true, &mut admin_lamports, &mut admin_data, &system_owner, false, 0),
+                AccountInfo::new(
+                    &config_key,
+                    false,
+                    true,
+                    &mut config_lamports,
+                    &mut config_data,
+                    &system_owner,
+                    false,
+                    0,
+                ),
+                AccountInfo::new(
+                    &program_data_key,
+                    false,
+                    false,
+                    &mut program_data_lamports,
+                    &mut data,
+                    &loader,
+                    false,
+                    0,
+                ),
+                AccountInfo::new(
+                    &system_program_key,
+                    false,
+                    false,
+                    &mut system_lamports,
+                    &mut system_data,
+                    &system_owner,
+                    true,
+                    0,
+                ),
+            ];
+
+            let parsed = InitializeConfigAccounts::parse(&program_id, &accounts).unwrap();
+            assert_eq!(parsed.upgrade_authority().unwrap(), authority);
+            if authority != Some(admin_key) {
+                assert_eq!(initialize_config(&program_id, &accounts), Err(SolfheError::Unauthorized.into()));
+            }
+        }
+    }
+    #[test]
+    fn test_create_pda_account_when_prefunded() {
+        stub_sysvars();
+        let program_id = Pubkey::new_unique();
+        let payer_key = Pubkey::new_unique();
+        let (config_key, bump) = ProgramConfig::find_address(&program_id);
+        let (system_owner, system_program_key) = (system_program::id(), system_program::id());
+        let rent = Rent::default().minimum_balance(ProgramConfig::LEN);
+
+        for prefunded in [0, 100, rent + 1] {
+            let (mut payer_lamports, mut config_lamports, mut system_lamports) = (rent, prefunded, 0);
+            let (mut payer_data, mut config_data, mut system_data) = (vec![], vec![], vec![]);
+            let payer =
+                AccountInfo::new(&payer_key, true, true, &mut payer_lamports, &mut payer_data, &system_owner, false, 0);
+            let config = AccountInfo::new(
+                &config_key,
+                false,
+                true,
+                &mut config_lamports,
+                &mut config_data,
+                &system_owner,
+                false,
+                0,
+            );
+            let system = AccountInfo::new(
+                &system_program_key,
+                false,
+                false,
+                &mut system_lamports,
+                &mut system_data,
+                &system_owner,
+                true,
+                0,
+            );
+
+            INVOKED.with(|invoked| invoked.borrow_mut().clear());
+            create_pda_account(&payer, &config, ProgramConfig::LEN, &program_id, &system, &[CONFIG_SEED, &[bump]])
+                .unwrap();
+            let invoked = INVOKED.with(|invoked| invoked.take());
+
+            let space = ProgramConfig::LEN as u64;
+            let expected = match prefunded {
+                0 => vec![system_instruction::create_account(&payer_key, &config_key, rent, space, &program_id)],
+                // Önceden gönderilen lamport'lar `create_account`'u engelleyemez
+                100 => vec![
+                    system_instruction::transfer(&payer_key, &config_key, rent - 100),
+                    system_instruction::allocate(&config_key, space),
+                    system_instruction::assign(&config_key, &program_id),
+                ],
+                _ => vec![
+                    system_instruction::allocate(&config_key, space),
+                    system_instruction::assign(&config_key, &program_id),
+                ],
+            };
+            assert_eq!(invoked, expected);
+        }
+    }
+}
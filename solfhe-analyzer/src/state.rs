@@ -0,0 +1,286 @@
+// Reklam veren kampanyalarının zincir üstü durumu (on-chain campaign state)
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{msg, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::error::SolfheError;
+
+pub const CAMPAIGN_SEED: &[u8] = b"campaign";
+pub const VAULT_SEED: &[u8] = b"vault";
+pub const REWARD_MINT_SEED: &[u8] = b"blinks_mint";
+pub const REWARD_AUTHORITY_SEED: &[u8] = b"reward_authority";
+pub const CLAIM_SEED: &[u8] = b"claim";
+pub const CONFIG_SEED: &[u8] = b"config";
+
+pub const MAX_REGISTERED_TREES: usize = 8;
+
+pub const MAX_TARGET_KEYWORDS: usize = 16;
+pub const MAX_KEYWORD_LEN: usize = 32;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CampaignStatus {
+    Active,
+    Paused,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct Campaign {
+    pub is_initialized: bool,
+    pub advertiser: Pubkey,
+    pub campaign_id: u64,
+    pub bump: u8,
+    pub status: CampaignStatus,
+    pub target_keywords: Vec<String>,
+    pub budget: u64,
+    pub bid_per_impression: u64,
+    pub bid_per_click: u64,
+    pub reward_per_dwell_second: u64,
+    pub start_slot: u64,
+    pub end_slot: u64,
+    pub vault_bump: u8,
+    /// Reklam verenin kasaya (vault) yatırdığı toplam lamport.
+    pub deposited: u64,
+    /// `RewardUser` ile kasadan kullanıcılara ödenen toplam lamport.
+    pub spent: u64,
+    /// Etkileşim raporlarını reklam veren adına onaylayan oracle; reklam veren
+    /// her zaman onaylayabilir.
+    pub attestor: Pubkey,
+}
+
+impl Campaign {
+    /// Hesap boyutu, en uzun anahtar kelime listesine göre sabitlenir; böylece
+    /// `UpdateCampaign` hesabı yeniden boyutlandırmak zorunda kalmaz.
+    pub const LEN: usize = 1 // is_initialized
+        + 32 // advertiser
+        + 8 // campaign_id
+        + 1 // bump
+        + 1 // status
+        + 4 + MAX_TARGET_KEYWORDS * (4 + MAX_KEYWORD_LEN) // target_keywords
+        + 8 // budget
+        + 8 // bid_per_impression
+        + 8 // bid_per_click
+        + 8 // reward_per_dwell_second
+        + 8 // start_slot
+        + 8 // end_slot
+        + 1 // vault_bump
+        + 8 // deposited
+        + 8 // spent
+        + 32; // attestor
+
+    pub fn find_address(program_id: &Pubkey, advertiser: &Pubkey, campaign_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[CAMPAIGN_SEED, advertiser.as_ref(), &campaign_id.to_le_bytes()],
+            program_id,
+        )
+    }
+
+    /// Kampanyanın ödüllerini tutan, program sahipli kasa hesabı.
+    pub fn find_vault_address(program_id: &Pubkey, campaign: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[VAULT_SEED, campaign.as_ref()], program_id)
+    }
+
+    /// Harcanabilir bütçe: yatırılan tutar ve bütçe tavanının küçüğünden harcanan düşülür.
+    pub fn remaining_budget(&self) -> u64 {
+        self.budget.min(self.deposited).saturating_sub(self.spent)
+    }
+
+    /// Raporu ortak imzalayabilecek hesaplar: reklam veren ve onun atadığı oracle.
+    pub fn accepts_attestor(&self, key: &Pubkey) -> bool {
+        *key == self.advertiser || *key == self.attestor
+    }
+
+    pub fn is_live(&self, slot: u64) -> bool {
+        self.status == CampaignStatus::Active && self.start_slot <= slot && slot <= self.end_slot
+    }
+
+    /// Borsh verisi sabit boyutlu hesaptan kısa olabilir, kalan baytlar sıfırdır.
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        let campaign = Self::deserialize(&mut &data[..])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        if !campaign.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        Ok(campaign)
+    }
+
+    pub fn pack(&self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        let bytes = self.try_to_vec().map_err(|_| ProgramError::InvalidAccountData)?;
+        if bytes.len() > dst.len() {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+        dst[..bytes.len()].copy_from_slice(&bytes);
+        dst[bytes.len()..].fill(0);
+        Ok(())
+    }
+}
+
+/// Kullanıcının bir kampanyadan en son ödül aldığı slot; aynı aralığın iki kez
+/// ödüllendirilmesini engeller.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct ClaimRecord {
+    pub is_initialized: bool,
+    pub last_claimed_slot: u64,
+}
+
+impl ClaimRecord {
+    pub const LEN: usize = 1 + 8;
+
+    pub fn find_address(program_id: &Pubkey, campaign: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[CLAIM_SEED, campaign.as_ref(), user.as_ref()], program_id)
+    }
+
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        let record = Self::deserialize(&mut &data[..])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        if !record.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        Ok(record)
+    }
+
+    pub fn pack(&self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        let bytes = self.try_to_vec().map_err(|_| ProgramError::InvalidAccountData)?;
+        if bytes.len() != dst.len() {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+        dst.copy_from_slice(&bytes);
+        Ok(())
+    }
+}
+
+/// Sıkıştırılmış hesaplarda tutulan veri türleri. Ayırıcı, `"solfhe:<TürAdı>"`
+/// SHA-256 özetinin ilk 8 baytıdır; zincir üstünde türler birbirinden ayrılır.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadKind {
+    UserProfile,
+    AnalysisResult,
+    EngagementReport,
+}
+
+impl PayloadKind {
+    pub const ALL: [PayloadKind; 3] = [
+        PayloadKind::UserProfile,
+        PayloadKind::AnalysisResult,
+        PayloadKind::EngagementReport,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PayloadKind::UserProfile => "UserProfile",
+            PayloadKind::AnalysisResult => "AnalysisResult",
+            PayloadKind::EngagementReport => "EngagementReport",
+        }
+    }
+
+    pub fn discriminator(&self) -> [u8; 8] {
+        match self {
+            PayloadKind::UserProfile => [152, 161, 81, 125, 206, 13, 239, 162],
+            PayloadKind::AnalysisResult => [89, 162, 188, 142, 80, 242, 228, 94],
+            PayloadKind::EngagementReport => [106, 31, 139, 200, 239, 104, 98, 166],
+        }
+    }
+
+    pub fn from_discriminator(discriminator: &[u8; 8]) -> Option<Self> {
+        Self::ALL.iter().copied().find(|k| k.discriminator() == *discriminator)
+    }
+
+    /// Ayırıcı beklenen türle eşleşmiyorsa hesap, bulunan türle birlikte loglanıp reddedilir.
+    pub fn expect(&self, discriminator: &[u8; 8]) -> Result<(), ProgramError> {
+        match Self::from_discriminator(discriminator) {
+            Some(kind) if kind == *self => Ok(()),
+            found => {
+                msg!(
+                    "Expected a {} payload, found {}",
+                    self.name(),
+                    found.map_or("an unknown payload", |k| k.name())
+                );
+                Err(SolfheError::InvalidDiscriminator.into())
+            }
+        }
+    }
+}
+
+/// Sıkıştırılmış hesapların yazılabileceği Light durum ağacı ve ona bağlı nullifier kuyruğu.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
+pub struct RegisteredTree {
+    pub merkle_tree: Pubkey,
+    pub nullifier_queue: Pubkey,
+}
+
+/// Program yapılandırması (PDA, seeds `["config"]`); yönetici kayıtlı ağaç listesini tutar.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct ProgramConfig {
+    pub is_initialized: bool,
+    pub admin: Pubkey,
+    pub bump: u8,
+    pub state_trees: Vec<RegisteredTree>,
+}
+
+impl ProgramConfig {
+    pub const LEN: usize = 1 // is_initialized
+        + 32 // admin
+        + 1 // bump
+        + 4 + MAX_REGISTERED_TREES * 64; // state_trees
+
+    pub fn find_address(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[CONFIG_SEED], program_id)
+    }
+
+    pub fn is_registered(&self, merkle_tree: &Pubkey, nullifier_queue: &Pubkey) -> bool {
+        self.state_trees
+            .iter()
+            .any(|t| t.merkle_tree == *merkle_tree && t.nullifier_queue == *nullifier_queue)
+    }
+
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        let config = Self::deserialize(&mut &data[..])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        if !config.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        Ok(config)
+    }
+
+    pub fn pack(&self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        let bytes = self.try_to_vec().map_err(|_| ProgramError::InvalidAccountData)?;
+        if bytes.len() > dst.len() {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+        dst[..bytes.len()].copy_from_slice(&bytes);
+        dst[bytes.len()..].fill(0);
+        Ok(())
+    }
+}
+
+pub fn validate_campaign_params(
+    target_keywords: &[String],
+    bid_per_impression: u64,
+    start_slot: u64,
+    end_slot: u64,
+) -> Result<(), ProgramError> {
+    if target_keywords.is_empty() || target_keywords.len() > MAX_TARGET_KEYWORDS {
+        return Err(SolfheError::InvalidCampaignParams.into());
+    }
+    if target_keywords
+        .iter()
+        .any(|k| k.is_empty() || k.len() > MAX_KEYWORD_LEN)
+    {
+        return Err(SolfheError::InvalidCampaignParams.into());
+    }
+    if bid_per_impression == 0 || start_slot >= end_slot {
+        return Err(SolfheError::InvalidCampaignParams.into());
+    }
+    Ok(())
+}
+
+/// Programın kontrol ettiği SPL "Blinks" ödül token'ı.
+pub fn find_reward_mint_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REWARD_MINT_SEED], program_id)
+}
+
+/// Blinks mint yetkilisi (program sahipli, veri taşımaz); harcanan kampanya
+/// lamport'ları basılan Blinks'in karşılığı olarak burada tutulur ve
+/// `RedeemBlinks` ile yakılan Blinks karşılığında ödenir.
+pub fn find_reward_authority_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REWARD_AUTHORITY_SEED], program_id)
+}
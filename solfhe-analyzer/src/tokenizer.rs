@@ -0,0 +1,138 @@
+// URL yol parçalarını anahtar kelimelere ayırır; zincir üstü `extract_keywords`
+// ile Tauri istemcisinin `analyze_link` fonksiyonu aynı kuralları kullanır.
+
+const FILE_EXTENSIONS: [&str; 19] = [
+    "html", "htm", "php", "asp", "aspx", "jsp", "js", "css", "json", "xml",
+    "png", "jpg", "jpeg", "gif", "svg", "webp", "pdf", "txt", "md",
+];
+
+// 8-4-4-4-12 onaltılık UUID biçimi
+const UUID_GROUPS: [usize; 5] = [8, 4, 4, 4, 12];
+const UUID_LEN: usize = 36;
+
+/// Bir yol parçasını (`my-post_title`, `getUserProfile`, `popup.html`) küçük
+/// harfli kelimelere böler; kimlik, hash ve UUID benzeri parçaları atar.
+pub fn tokenize_segment(segment: &str) -> Vec<String> {
+    let segment = strip_extension(segment);
+    let segment = strip_uuids(segment);
+    segment
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty() && !is_identifier(token))
+        .flat_map(split_camel_case)
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+/// Bütün bir URL'yi `/ ? & = #` ve `:` ayraçlarından bölüp her parçayı kelimelere ayırır.
+pub fn tokenize_url(url: &str) -> Vec<String> {
+    url.split(['/', '?', '&', '=', '#', ':'])
+        .flat_map(tokenize_segment)
+        .collect()
+}
+
+fn strip_extension(segment: &str) -> &str {
+    match segment.rsplit_once('.') {
+        Some((stem, extension))
+            if !stem.is_empty()
+                && FILE_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str()) =>
+        {
+            stem
+        },
+        _ => segment,
+    }
+}
+
+fn is_uuid(candidate: &[u8]) -> bool {
+    let mut groups = candidate.split(|&b| b == b'-');
+    UUID_GROUPS.iter().all(|&len| {
+        matches!(groups.next(), Some(group) if group.len() == len && group.iter().all(u8::is_ascii_hexdigit))
+    }) && groups.next().is_none()
+}
+
+// UUID'ler tirelerle bölünmeden önce ayıklanır; aksi halde 4 haneli gruplar kelime sanılır
+fn strip_uuids(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut result = String::with_capacity(segment.len());
+    let mut start = 0;
+    let mut i = 0;
+    while i + UUID_LEN <= bytes.len() {
+        if is_uuid(&bytes[i..i + UUID_LEN]) {
+            // Eşleşen pencere tamamen ASCII, bu yüzden sınırlar geçerli karakter sınırlarıdır
+            result.push_str(&segment[start..i]);
+            result.push('-');
+            i += UUID_LEN;
+            start = i;
+        } else {
+            i += 1;
+        }
+    }
+    result.push_str(&segment[start..]);
+    result
+}
+
+/// Sayılar, onaltılık hash'ler, base58 adresler ve uzantı kimlikleri gibi
+/// okunabilir kelime olmayan parçalar.
+fn is_identifier(token: &str) -> bool {
+    let len = token.chars().count();
+    let has_digit = token.chars().any(|c| c.is_ascii_digit());
+    let has_letter = token.chars().any(char::is_alphabetic);
+
+    if !has_letter {
+        return true;
+    }
+    if token.chars().all(|c| c.is_ascii_hexdigit()) && (len >= 12 || (len >= 8 && has_digit)) {
+        return true;
+    }
+    // base58: rakam, büyük ve küçük harf sık sık yer değiştirir
+    let has_upper = token.chars().any(char::is_uppercase);
+    let has_lower = token.chars().any(char::is_lowercase);
+    if len >= 20 && has_digit && has_upper && has_lower && class_changes(token) * 3 >= len {
+        return true;
+    }
+    // Chrome uzantı kimlikleri gibi sesli harfi çok az olan uzun parçalar
+    if len >= 12 {
+        let vowels = token.chars().filter(|c| "aeiouyAEIOUY".contains(*c)).count();
+        if (vowels as f32) < len as f32 * 0.2 {
+            return true;
+        }
+    }
+    false
+}
+
+fn char_class(c: char) -> u8 {
+    if c.is_ascii_digit() {
+        0
+    } else if c.is_uppercase() {
+        1
+    } else {
+        2
+    }
+}
+
+fn class_changes(token: &str) -> usize {
+    let classes: Vec<u8> = token.chars().map(char_class).collect();
+    classes.windows(2).filter(|pair| pair[0] != pair[1]).count()
+}
+
+/// `getUserProfile` -> get, User, Profile; `HTMLParser` -> HTML, Parser.
+/// Harf ile rakam arası bölünmez (`web3`, `mp4`).
+fn split_camel_case(token: &str) -> Vec<&str> {
+    let chars: Vec<(usize, char)> = token.char_indices().collect();
+    let mut words = Vec::new();
+    let mut start = 0;
+    for i in 1..chars.len() {
+        let (index, current) = chars[i];
+        let previous = chars[i - 1].1;
+        let next_is_lower = matches!(chars.get(i + 1), Some(&(_, c)) if c.is_lowercase());
+        let boundary = current.is_uppercase()
+            && (previous.is_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_uppercase() && next_is_lower));
+        if boundary {
+            words.push(&token[start..index]);
+            start = index;
+        }
+    }
+    words.push(&token[start..]);
+    words
+}
@@ -0,0 +1,581 @@
+// Solana Actions client (https://solana.com/docs/advanced/actions). A matched
+// blink's action URL is resolved through the site's `actions.json` rules, its
+// GET metadata becomes an `ActionPreview` for the UI, and the chosen action is
+// POSTed with the user's account to get back the transaction to sign in-app.
+// That transaction is checked as a `PendingTransaction` before it is signed.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::time::Duration;
+
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use solana_sdk::{
+    hash::Hash,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    transaction::VersionedTransaction,
+};
+use url::Url;
+
+// Send + Sync so errors can cross the awaits in Tauri commands
+pub type ActionResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+const ACTION_SCHEME: &str = "solana-action:";
+/// An action that has not answered by then is treated as unreachable.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionParameter {
+    pub name: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkedAction {
+    /// May contain `{name}` placeholders for the parameters.
+    pub href: String,
+    pub label: String,
+    #[serde(default)]
+    pub parameters: Vec<ActionParameter>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionLinks {
+    pub actions: Vec<LinkedAction>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionError {
+    pub message: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionGetResponse {
+    pub icon: String,
+    pub title: String,
+    pub description: String,
+    pub label: String,
+    #[serde(default)]
+    pub disabled: bool,
+    #[serde(default)]
+    pub links: Option<ActionLinks>,
+    #[serde(default)]
+    pub error: Option<ActionError>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ActionPostRequest {
+    /// Base58 public key of the account that signs the transaction.
+    pub account: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ActionPostResponse {
+    /// Base64 serialized transaction.
+    pub transaction: String,
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+impl ActionPostResponse {
+    pub fn transaction_bytes(&self) -> ActionResult<Vec<u8>> {
+        Ok(general_purpose::STANDARD.decode(&self.transaction)?)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionRule {
+    pub path_pattern: String,
+    pub api_path: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ActionsJson {
+    pub rules: Vec<ActionRule>,
+}
+
+impl ActionsJson {
+    /// The action endpoint for a page on the site, from the first matching rule.
+    pub fn resolve(&self, url: &Url) -> Option<Url> {
+        self.rules.iter().find_map(|rule| {
+            let captures = match_path(&rule.path_pattern, url.path())?;
+            let api_path = substitute(&rule.api_path, &captures);
+            let mut resolved = match Url::parse(&api_path) {
+                Ok(absolute) => absolute,
+                Err(_) => url.join(&api_path).ok()?,
+            };
+            if resolved.query().is_none() {
+                resolved.set_query(url.query());
+            }
+            Some(resolved)
+        })
+    }
+}
+
+fn segments(path: &str) -> Vec<&str> {
+    path.split('/').filter(|segment| !segment.is_empty()).collect()
+}
+
+/// Matches `path` against a rule's pattern: `*` is one segment and a trailing
+/// `**` is any remaining segments. Returns what the wildcards matched.
+fn match_path(pattern: &str, path: &str) -> Option<Vec<String>> {
+    let pattern = segments(pattern);
+    let path = segments(path);
+    let mut captures = Vec::new();
+    for (i, part) in pattern.iter().enumerate() {
+        match *part {
+            "**" if i + 1 == pattern.len() => {
+                captures.push(path.get(i..).unwrap_or_default().join("/"));
+                return Some(captures);
+            },
+            "*" => captures.push(path.get(i)?.to_string()),
+            literal => {
+                if path.get(i) != Some(&literal) {
+                    return None;
+                }
+            },
+        }
+    }
+    (pattern.len() == path.len()).then_some(captures)
+}
+
+/// Replaces the wildcards of a rule's `apiPath` with the matched segments.
+fn substitute(api_path: &str, captures: &[String]) -> String {
+    let mut captures = captures.iter();
+    let mut parts: Vec<&str> = Vec::new();
+    for part in api_path.split('/') {
+        if part == "*" || part == "**" {
+            match captures.next() {
+                Some(capture) if !capture.is_empty() => parts.push(capture),
+                _ => {},
+            }
+        } else {
+            parts.push(part);
+        }
+    }
+    parts.join("/")
+}
+
+/// One button of the action, with an absolute `href`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ActionButton {
+    pub label: String,
+    pub href: String,
+    pub parameters: Vec<ActionParameter>,
+}
+
+impl ActionButton {
+    /// The button's URL with its `{name}` placeholders filled in.
+    pub fn fill(&self, values: &HashMap<String, String>) -> ActionResult<Url> {
+        let mut href = self.href.clone();
+        for parameter in &self.parameters {
+            let value = values.get(&parameter.name).map(String::as_str).unwrap_or_default();
+            if value.is_empty() && parameter.required {
+                return Err(format!("{} is required", parameter.label.as_deref().unwrap_or(&parameter.name)).into());
+            }
+            let encoded: String = url::form_urlencoded::byte_serialize(value.as_bytes()).collect();
+            href = href.replace(&format!("{{{}}}", parameter.name), &encoded);
+        }
+        Ok(Url::parse(&href)?)
+    }
+}
+
+/// What the UI renders for a matched blink.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ActionPreview {
+    pub action_url: String,
+    pub icon: String,
+    pub title: String,
+    pub description: String,
+    pub disabled: bool,
+    pub error: Option<String>,
+    pub buttons: Vec<ActionButton>,
+}
+
+impl ActionPreview {
+    fn new(action_url: &Url, response: ActionGetResponse) -> ActionResult<Self> {
+        let buttons = match response.links {
+            Some(links) if !links.actions.is_empty() => links
+                .actions
+                .into_iter()
+                .map(|action| {
+                    Ok(ActionButton {
+                        href: resolve_href(action_url, &action.href)?,
+                        label: action.label,
+                        parameters: action.parameters,
+                    })
+                })
+                .collect::<Result<_, url::ParseError>>()?,
+            // Without linked actions the action URL itself is the one button
+            _ => vec![ActionButton {
+                label: response.label,
+                href: action_url.to_string(),
+                parameters: Vec::new(),
+            }],
+        };
+        Ok(Self {
+            action_url: action_url.to_string(),
+            icon: action_url.join(&response.icon)?.to_string(),
+            title: response.title,
+            description: response.description,
+            disabled: response.disabled,
+            error: response.error.map(|error| error.message),
+            buttons,
+        })
+    }
+}
+
+/// Resolves a linked action's `href` against the action URL. Only the text
+/// before the first `{name}` placeholder is resolved; the rest is kept as the
+/// action wrote it, so placeholders survive and encoded braces stay encoded.
+fn resolve_href(action_url: &Url, href: &str) -> Result<String, url::ParseError> {
+    let (base, template) = href.find('{').map_or((href, ""), |index| href.split_at(index));
+    Ok(format!("{}{}", action_url.join(base)?, template))
+}
+
+/// An action's transaction that has been checked but not signed yet. The action
+/// chooses every account in it, so we only sign when it makes our account the
+/// fee payer and every other signer has already signed.
+#[derive(Clone, Debug)]
+pub struct PendingTransaction {
+    transaction: VersionedTransaction,
+}
+
+impl PendingTransaction {
+    pub fn decode(serialized: &[u8], account: &Pubkey) -> ActionResult<Self> {
+        let mut transaction: VersionedTransaction = bincode::deserialize(serialized)?;
+        let required_signatures = transaction.message.header().num_required_signatures as usize;
+        transaction.signatures.resize(required_signatures, Signature::default());
+        let signers = transaction.message.static_account_keys().get(..required_signatures).unwrap_or_default();
+        match signers.first() {
+            Some(fee_payer) if fee_payer == account => {},
+            Some(fee_payer) => return Err(format!("The action's transaction is paid by {}, not by {}", fee_payer, account).into()),
+            None => return Err("The action's transaction has no fee payer".into()),
+        }
+
+        let message = transaction.message.serialize();
+        for (signer, signature) in signers.iter().zip(&transaction.signatures).skip(1) {
+            if signer == account {
+                continue;
+            }
+            if !signature.verify(signer.as_ref(), &message) {
+                return Err(format!("The action's transaction is missing the signature of {}", signer).into());
+            }
+        }
+        Ok(Self { transaction })
+    }
+
+    pub fn fee_payer(&self) -> Pubkey {
+        self.transaction.message.static_account_keys()[0]
+    }
+
+    /// Identifies the transaction the user was shown, so only that one is signed.
+    pub fn message_hash(&self) -> Hash {
+        self.transaction.message.hash()
+    }
+
+    /// The transaction as the action built it, for simulation.
+    pub fn unsigned(&self) -> &VersionedTransaction {
+        &self.transaction
+    }
+
+    /// Adds our signature wherever the message asks for it; the action's own
+    /// signatures are kept.
+    pub fn sign(mut self, keypair: &Keypair) -> VersionedTransaction {
+        let signature = keypair.sign_message(&self.transaction.message.serialize());
+        let required_signatures = self.transaction.signatures.len();
+        for (key, slot) in self.transaction.message.static_account_keys()[..required_signatures]
+            .iter()
+            .zip(&mut self.transaction.signatures)
+        {
+            if *key == keypair.pubkey() {
+                *slot = signature;
+            }
+        }
+        self.transaction
+    }
+}
+
+#[derive(Clone)]
+pub struct ActionsClient {
+    http: reqwest::Client,
+}
+
+impl Default for ActionsClient {
+    fn default() -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .expect("the HTTP client could not be initialized");
+        Self { http }
+    }
+}
+
+impl ActionsClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The action endpoint for a `solana-action:` URL, a blink URL carrying
+    /// one in its `action` parameter, or a page covered by its site's
+    /// `actions.json`. Anything else is taken to be the endpoint itself.
+    pub async fn resolve(&self, url: &str) -> ActionResult<Url> {
+        if let Some(action) = url.strip_prefix(ACTION_SCHEME) {
+            return Ok(Url::parse(action)?);
+        }
+        let url = Url::parse(url)?;
+        if let Some((_, action)) = url.query_pairs().find(|(key, _)| key == "action") {
+            if let Some(action) = action.strip_prefix(ACTION_SCHEME) {
+                return Ok(Url::parse(action)?);
+            }
+        }
+
+        let actions_json = url.join("/actions.json")?;
+        let rules = match self.http.get(actions_json).send().await {
+            Ok(response) if response.status().is_success() => response.json::<ActionsJson>().await.ok(),
+            _ => None,
+        };
+        Ok(rules.and_then(|rules| rules.resolve(&url)).unwrap_or(url))
+    }
+
+    pub async fn get(&self, action_url: &Url) -> ActionResult<ActionGetResponse> {
+        let response = self.http.get(action_url.clone()).send().await?;
+        Self::json(response).await
+    }
+
+    pub async fn preview(&self, url: &str) -> ActionResult<ActionPreview> {
+        let action_url = self.resolve(url).await?;
+        let response = self.get(&action_url).await?;
+        ActionPreview::new(&action_url, response)
+    }
+
+    pub async fn post(&self, href: &Url, account: &str) -> ActionResult<ActionPostResponse> {
+        let request = ActionPostRequest { account: account.to_string() };
+        let response = self.http.post(href.clone()).json(&request).send().await?;
+        Self::json(response).await
+    }
+
+    /// The body as JSON, or the action's `{ "message": ... }` error.
+    async fn json<T: serde::de::DeserializeOwned>(response: reqwest::Response) -> ActionResult<T> {
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            let message = serde_json::from_str::<ActionError>(&body).map(|error| error.message).unwrap_or(body);
+            return Err(format!("action returned {}: {}", status, message).into());
+        }
+        Ok(serde_json::from_str(&body)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    type Requests = Arc<Mutex<Vec<(String, String, String)>>>;
+
+    /// A minimal HTTP server on a free local port: `route(method, path, body)`
+    /// returns the status and JSON body. Records every request it serves.
+    fn stub_server(route: fn(&str, &str, &str) -> (u16, String)) -> (String, Requests) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let origin = format!("http://{}", listener.local_addr().unwrap());
+        let requests: Requests = Arc::default();
+        let recorded = requests.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut content_length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = header.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                let body = String::from_utf8(body).unwrap();
+
+                let mut parts = request_line.split_whitespace();
+                let (method, path) = (parts.next().unwrap().to_string(), parts.next().unwrap().to_string());
+                let (status, response) = route(&method, &path, &body);
+                recorded.lock().unwrap().push((method, path, body));
+                write!(
+                    stream,
+                    "HTTP/1.1 {} Stub\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    response.len(),
+                    response
+                )
+                .unwrap();
+            }
+        });
+        (origin, requests)
+    }
+
+    fn solphi_route(method: &str, path: &str, body: &str) -> (u16, String) {
+        match (method, path) {
+            ("GET", "/actions.json") => (
+                200,
+                r#"{"rules": [
+                    {"pathPattern": "/*", "apiPath": "/api/actions/*"},
+                    {"pathPattern": "/api/actions/**", "apiPath": "/api/actions/**"}
+                ]}"#
+                .to_string(),
+            ),
+            ("GET", "/api/actions/solphi") => (
+                200,
+                r#"{"type": "action", "title": "Solφ Advertisement", "icon": "/icon.gif",
+                    "description": "Earn SOL by watching ads.", "label": "Transfer", "disabled": false,
+                    "links": {"actions": [{"label": "Send",
+                        "href": "/api/actions/solphi?receiverWallet={receiverWallet}",
+                        "parameters": [{"name": "receiverWallet", "label": "Receiver Wallet", "required": true}]}]}}"#
+                    .to_string(),
+            ),
+            ("POST", path) if path.starts_with("/api/actions/solphi?") => {
+                let request: ActionPostRequest = serde_json::from_str(body).unwrap();
+                if request.account.is_empty() {
+                    return (400, r#"{"message": "Invalid \"account\" provided"}"#.to_string());
+                }
+                (200, r#"{"transaction": "AQID", "message": "Thanks for watching"}"#.to_string())
+            },
+            _ => (404, r#"{"message": "not found"}"#.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_actions_json_rules() {
+        let rules: ActionsJson = serde_json::from_str(
+            r#"{"rules": [
+                {"pathPattern": "/buy/*", "apiPath": "https://api.example.com/v1/buy/*"},
+                {"pathPattern": "/*", "apiPath": "/api/actions/*"},
+                {"pathPattern": "/api/actions/**", "apiPath": "/api/actions/**"}
+            ]}"#,
+        )
+        .unwrap();
+        let resolve = |url: &str| rules.resolve(&Url::parse(url).unwrap()).map(String::from);
+
+        assert_eq!(resolve("https://site.example/buy/sol?amount=1").as_deref(), Some("https://api.example.com/v1/buy/sol?amount=1"));
+        assert_eq!(resolve("https://site.example/donate").as_deref(), Some("https://site.example/api/actions/donate"));
+        assert_eq!(
+            resolve("https://site.example/api/actions/memo/send").as_deref(),
+            Some("https://site.example/api/actions/memo/send")
+        );
+        assert_eq!(resolve("https://site.example/a/b"), None);
+    }
+
+    #[test]
+    fn test_resolve_href() {
+        let action_url = Url::parse("https://api.example.com/v1/donate").unwrap();
+        let resolve = |href| resolve_href(&action_url, href).unwrap();
+        assert_eq!(resolve("/v1/donate/{amount}"), "https://api.example.com/v1/donate/{amount}");
+        assert_eq!(resolve("?amount={amount}&memo={memo}"), "https://api.example.com/v1/donate?amount={amount}&memo={memo}");
+        assert_eq!(resolve("https://other.example/give?to=sol"), "https://other.example/give?to=sol");
+        // Braces the action encoded itself are not placeholders
+        assert_eq!(resolve("/v1/donate?note=%7Bhi%7D&amount={amount}"), "https://api.example.com/v1/donate?note=%7Bhi%7D&amount={amount}");
+    }
+
+    #[tokio::test]
+    async fn test_preview_and_post_against_stub() {
+        let (origin, requests) = stub_server(solphi_route);
+        let client = ActionsClient::new();
+
+        // A page URL is resolved through actions.json
+        let preview = client.preview(&format!("{}/solphi", origin)).await.unwrap();
+        assert_eq!(preview.action_url, format!("{}/api/actions/solphi", origin));
+        assert_eq!(preview.title, "Solφ Advertisement");
+        assert_eq!(preview.icon, format!("{}/icon.gif", origin));
+        assert_eq!(preview.buttons.len(), 1);
+        let button = &preview.buttons[0];
+        assert_eq!(button.href, format!("{}/api/actions/solphi?receiverWallet={{receiverWallet}}", origin));
+        assert_eq!(button.parameters[0].label.as_deref(), Some("Receiver Wallet"));
+
+        assert_eq!(button.fill(&HashMap::new()).unwrap_err().to_string(), "Receiver Wallet is required");
+        let values = HashMap::from([("receiverWallet".to_string(), "a514vQv8WeriXr6JYwTMB9gurRJVJW7yqvXghnJFT9Q".to_string())]);
+        let href = button.fill(&values).unwrap();
+
+        let response = client.post(&href, "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM").await.unwrap();
+        assert_eq!(response.transaction_bytes().unwrap(), vec![1, 2, 3]);
+        assert_eq!(response.message.as_deref(), Some("Thanks for watching"));
+
+        let error = client.post(&href, "").await.unwrap_err().to_string();
+        assert!(error.contains("400") && error.contains("Invalid \"account\" provided"), "{}", error);
+
+        let requests = requests.lock().unwrap();
+        let (method, path, body) = &requests[2];
+        assert_eq!((method.as_str(), path.as_str()), ("POST", "/api/actions/solphi?receiverWallet=a514vQv8WeriXr6JYwTMB9gurRJVJW7yqvXghnJFT9Q"));
+        assert_eq!(body, r#"{"account":"9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"}"#);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_without_actions_json() {
+        let (origin, _) = stub_server(|_, _, _| (404, "{}".to_string()));
+        let client = ActionsClient::new();
+        let direct = format!("{}/api/action", origin);
+        assert_eq!(client.resolve(&direct).await.unwrap().as_str(), direct);
+        assert_eq!(client.resolve(&format!("solana-action:{}", direct)).await.unwrap().as_str(), direct);
+        let blink = format!("https://dial.to/?action=solana-action:{}&cluster=devnet", direct);
+        assert_eq!(client.resolve(&blink).await.unwrap().as_str(), direct);
+    }
+
+    /// An instruction `from` has to sign, paid by `payer`, serialized as an action returns it.
+    fn action_transaction(payer: &Pubkey, from: &Keypair, signed_by_from: bool) -> Vec<u8> {
+        use solana_sdk::instruction::{AccountMeta, Instruction};
+        use solana_sdk::message::{Message, VersionedMessage};
+
+        let instruction = Instruction::new_with_bytes(Pubkey::new_unique(), &[], vec![AccountMeta::new(from.pubkey(), true)]);
+        let message = VersionedMessage::Legacy(Message::new(&[instruction], Some(payer)));
+        let mut signatures = vec![Signature::default(); 2];
+        if signed_by_from {
+            signatures[1] = from.sign_message(&message.serialize());
+        }
+        bincode::serialize(&VersionedTransaction { signatures, message }).unwrap()
+    }
+
+    #[test]
+    fn test_pending_transaction_checks() {
+        let account = Keypair::new();
+        let other = Keypair::new();
+
+        let error = PendingTransaction::decode(&action_transaction(&other.pubkey(), &account, false), &account.pubkey())
+            .unwrap_err()
+            .to_string();
+        assert_eq!(error, format!("The action's transaction is paid by {}, not by {}", other.pubkey(), account.pubkey()));
+
+        let error = PendingTransaction::decode(&action_transaction(&account.pubkey(), &other, false), &account.pubkey())
+            .unwrap_err()
+            .to_string();
+        assert_eq!(error, format!("The action's transaction is missing the signature of {}", other.pubkey()));
+
+        // We pay the fee and sign for ourselves; the action's signature is kept
+        let pending = PendingTransaction::decode(&action_transaction(&account.pubkey(), &other, true), &account.pubkey()).unwrap();
+        assert_eq!(pending.fee_payer(), account.pubkey());
+        assert_eq!(pending.unsigned().signatures[0], Signature::default());
+        let transaction = pending.sign(&account);
+        assert_eq!(transaction.verify_with_results(), vec![true, true]);
+
+        // When we are the only signer there is nothing else to check
+        let pending = PendingTransaction::decode(&action_transaction(&account.pubkey(), &account, false), &account.pubkey()).unwrap();
+        assert_eq!(pending.sign(&account).verify_with_results(), vec![true]);
+    }
+}
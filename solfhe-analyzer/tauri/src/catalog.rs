@@ -0,0 +1,346 @@
+// The blink catalog: the ads/blinks the matcher ranks, kept in a JSON file
+// (schema: data/blinks.schema.json) in the app's data directory. The bundled
+// catalog is installed there on first run: data/blinks.json in release builds
+// and data/blinks.dev.json, whose actions are served from localhost, in debug
+// builds. Each kind of build keeps its own file, so a release build never
+// loads a development catalog. The file is validated on load and reloaded
+// whenever it changes; an invalid edit keeps the previous catalog.
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, PoisonError, RwLock};
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use url::{Host, Url};
+
+use crate::matcher::Candidate;
+use crate::taxonomy;
+
+#[cfg(debug_assertions)]
+static BUNDLED_CATALOG: &str = include_str!("../data/blinks.dev.json");
+#[cfg(not(debug_assertions))]
+static BUNDLED_CATALOG: &str = include_str!("../data/blinks.json");
+
+#[cfg(debug_assertions)]
+const CATALOG_FILE: &str = "blinks.dev.json";
+#[cfg(not(debug_assertions))]
+const CATALOG_FILE: &str = "blinks.json";
+
+/// Local action servers and the localnet cluster are for development only.
+const ALLOW_LOCAL: bool = cfg!(debug_assertions);
+
+/// The catalog file version this build understands.
+pub const CATALOG_VERSION: u32 = 1;
+
+const DIAL_TO: &str = "https://dial.to/developer";
+
+pub type SharedCatalog = Arc<RwLock<Catalog>>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Cluster {
+    Mainnet,
+    Devnet,
+    Testnet,
+    /// A local validator; only accepted in debug builds.
+    Localnet,
+}
+
+impl Cluster {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Cluster::Mainnet => "mainnet",
+            Cluster::Devnet => "devnet",
+            Cluster::Testnet => "testnet",
+            Cluster::Localnet => "localnet",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlinkEntry {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// Taxonomy paths, e.g. `Crypto > DeFi > Lending`.
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// The Solana Action endpoint.
+    pub action_url: String,
+    pub cluster: Cluster,
+    /// Lamports the advertiser pays per impression.
+    #[serde(default)]
+    pub bid: Option<u64>,
+}
+
+impl BlinkEntry {
+    /// The dial.to page that renders the action. dial.to cannot reach a local
+    /// validator, so localnet actions are linked directly.
+    pub fn blink_url(&self) -> String {
+        if self.cluster == Cluster::Localnet {
+            return self.action_url.clone();
+        }
+        Url::parse_with_params(DIAL_TO, &[("url", self.action_url.as_str()), ("cluster", self.cluster.as_str())])
+            .map(String::from)
+            .unwrap_or_else(|_| self.action_url.clone())
+    }
+
+    fn validate(&self, allow_local: bool) -> Result<(), String> {
+        let valid_id = !self.id.is_empty()
+            && self.id.split('-').all(|part| {
+                !part.is_empty() && part.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+            });
+        if !valid_id {
+            return Err(format!("id {:?} must be lowercase words separated by hyphens", self.id));
+        }
+        if self.title.trim().is_empty() {
+            return Err(format!("{}: title is empty", self.id));
+        }
+        if self.keywords.iter().any(|keyword| keyword.trim().is_empty()) {
+            return Err(format!("{}: keywords must not be empty", self.id));
+        }
+        let is_local = match Url::parse(&self.action_url) {
+            Ok(url) if url.scheme() == "http" || url.scheme() == "https" => is_local_host(&url),
+            _ => return Err(format!("{}: action_url {:?} is not an http(s) URL", self.id, self.action_url)),
+        };
+        if is_local && self.cluster != Cluster::Localnet {
+            return Err(format!("{}: action_url {:?} is local, so cluster must be localnet", self.id, self.action_url));
+        }
+        if !allow_local && (is_local || self.cluster == Cluster::Localnet) {
+            return Err(format!("{}: local actions are only allowed in development builds", self.id));
+        }
+        let taxonomy = taxonomy::embedded();
+        if let Some(category) = self.categories.iter().find(|category| !taxonomy.contains(category)) {
+            return Err(format!("{}: {:?} is not a taxonomy category", self.id, category));
+        }
+        Ok(())
+    }
+
+    pub fn to_candidate(&self) -> Candidate {
+        Candidate {
+            id: self.id.clone(),
+            title: self.title.clone(),
+            description: self.description.clone(),
+            keywords: self.keywords.clone(),
+            categories: self.categories.clone(),
+            url: self.blink_url(),
+            action_url: self.action_url.clone(),
+            bid: self.bid.unwrap_or(0),
+        }
+    }
+}
+
+fn is_local_host(url: &Url) -> bool {
+    match url.host() {
+        Some(Host::Domain(domain)) => domain == "localhost" || domain.ends_with(".localhost"),
+        Some(Host::Ipv4(ip)) => ip.is_loopback() || ip.is_unspecified(),
+        Some(Host::Ipv6(ip)) => ip.is_loopback() || ip.is_unspecified(),
+        None => false,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CatalogFile {
+    #[serde(rename = "$schema", default)]
+    _schema: Option<String>,
+    version: u32,
+    blinks: Vec<BlinkEntry>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Catalog {
+    pub blinks: Vec<BlinkEntry>,
+}
+
+impl Catalog {
+    pub fn default_path() -> PathBuf {
+        dirs::data_local_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("solfhe")
+            .join(CATALOG_FILE)
+    }
+
+    pub fn bundled() -> Self {
+        Self::parse(BUNDLED_CATALOG).expect("bundled blink catalog is valid")
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, Box<dyn Error>> {
+        Self::parse_with(contents, ALLOW_LOCAL)
+    }
+
+    fn parse_with(contents: &str, allow_local: bool) -> Result<Self, Box<dyn Error>> {
+        let file: CatalogFile = serde_json::from_str(contents)?;
+        if file.version != CATALOG_VERSION {
+            return Err(format!(
+                "blink catalog version {} is not supported (expected {})",
+                file.version, CATALOG_VERSION
+            )
+            .into());
+        }
+        let mut ids = HashSet::new();
+        for blink in &file.blinks {
+            blink.validate(allow_local)?;
+            if !ids.insert(blink.id.as_str()) {
+                return Err(format!("blink {} is listed twice", blink.id).into());
+            }
+        }
+        Ok(Self { blinks: file.blinks })
+    }
+
+    /// Loads the catalog, installing the bundled one first if there is none.
+    pub fn load_or_install(path: &Path) -> Result<Self, Box<dyn Error>> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Self::parse(&contents),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                fs::write(path, BUNDLED_CATALOG)?;
+                Ok(Self::bundled())
+            },
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn candidates(&self) -> Vec<Candidate> {
+        self.blinks.iter().map(BlinkEntry::to_candidate).collect()
+    }
+}
+
+fn reload(path: &Path, shared: &SharedCatalog) {
+    let catalog = fs::read_to_string(path)
+        .map_err(Box::<dyn Error>::from)
+        .and_then(|contents| Catalog::parse(&contents));
+    match catalog {
+        Ok(catalog) => {
+            println!("Blink catalog reloaded: {} blinks", catalog.blinks.len());
+            *shared.write().unwrap_or_else(PoisonError::into_inner) = catalog;
+        },
+        Err(e) => println!("Blink catalog {} is invalid, keeping the previous one: {}", path.display(), e),
+    }
+}
+
+/// Reloads `shared` whenever the catalog file changes, until the returned
+/// watcher is dropped.
+pub fn watch(path: &Path, shared: SharedCatalog) -> notify::Result<RecommendedWatcher> {
+    let watched = path.to_path_buf();
+    let file_name = path.file_name().map(|name| name.to_os_string());
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+        let Ok(event) = event else {
+            return;
+        };
+        let is_write = matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_));
+        if is_write && event.paths.iter().any(|p| p.file_name() == file_name.as_deref()) {
+            reload(&watched, &shared);
+        }
+    })?;
+    // Editors often save by replacing the file, so its directory is watched
+    watcher.watch(path.parent().unwrap_or(Path::new(".")), RecursiveMode::NonRecursive)?;
+    Ok(watcher)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    fn catalog_json(blinks: &str) -> String {
+        format!(r#"{{"version": 1, "blinks": [{}]}}"#, blinks)
+    }
+
+    const LENDING: &str = r#"{"id": "lend-v2", "title": "Lend", "description": "Borrow and lend",
+        "categories": ["Crypto > DeFi > Lending"], "action_url": "https://lend.example/api/action",
+        "cluster": "devnet", "bid": 5000}"#;
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_bundled_dev_catalog() {
+        let catalog = Catalog::bundled();
+        assert_eq!(catalog.blinks.len(), 10);
+        let earn = &catalog.blinks[0];
+        assert_eq!(earn.id, "superteam-earn");
+        assert_eq!(earn.cluster, Cluster::Localnet);
+        assert_eq!(earn.blink_url(), "http://localhost:3001/api/action");
+        assert!(Catalog::default_path().ends_with("solfhe/blinks.dev.json"));
+    }
+
+    #[test]
+    fn test_release_catalog() {
+        // The release catalog must load without local actions, and the development one must not
+        let release = Catalog::parse_with(include_str!("../data/blinks.json"), false).unwrap();
+        assert!(release.blinks.iter().all(|blink| blink.cluster != Cluster::Localnet));
+        assert!(Catalog::parse_with(include_str!("../data/blinks.dev.json"), false).is_err());
+    }
+
+    #[test]
+    fn test_local_actions() {
+        let local = LENDING.replace("https://lend.example", "http://127.0.0.1:3000");
+        let error = |json: String, allow_local: bool| Catalog::parse_with(&json, allow_local).unwrap_err().to_string();
+        assert!(error(catalog_json(&local), true).contains("cluster must be localnet"));
+        assert!(error(catalog_json(&LENDING.replace("lend.example", "api.localhost")), true).contains("must be localnet"));
+
+        let localnet = local.replace("devnet", "localnet");
+        let catalog = Catalog::parse_with(&catalog_json(&localnet), true).unwrap();
+        assert_eq!(catalog.blinks[0].blink_url(), "http://127.0.0.1:3000/api/action");
+        assert!(error(catalog_json(&localnet), false).contains("development builds"));
+        assert!(error(catalog_json(&LENDING.replace("devnet", "localnet")), false).contains("development builds"));
+        assert!(Catalog::parse_with(&catalog_json(LENDING), false).is_ok());
+    }
+
+    #[test]
+    fn test_parse_validates_entries() {
+        let catalog = Catalog::parse(&catalog_json(LENDING)).unwrap();
+        assert_eq!(catalog.blinks[0].cluster, Cluster::Devnet);
+        assert_eq!(catalog.blinks[0].bid, Some(5000));
+
+        let error = |json: String| Catalog::parse(&json).unwrap_err().to_string();
+        assert!(error(r#"{"version": 2, "blinks": []}"#.to_string()).contains("version 2"));
+        assert!(error(catalog_json(&format!("{},{}", LENDING, LENDING))).contains("listed twice"));
+        assert!(error(catalog_json(&LENDING.replace("lend-v2", "Lend V2"))).contains("lowercase words"));
+        assert!(error(catalog_json(&LENDING.replace("https://lend.example", "ftp://lend.example"))).contains("http(s)"));
+        assert!(error(catalog_json(&LENDING.replace("Lending", "Cooking"))).contains("not a taxonomy category"));
+        assert!(error(catalog_json(&LENDING.replace("devnet", "regtest"))).contains("unknown variant"));
+        assert!(error(catalog_json(&LENDING.replace("\"bid\"", "\"price\""))).contains("unknown field"));
+    }
+
+    #[test]
+    fn test_load_or_install_and_hot_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("solfhe").join("blinks.json");
+        let shared: SharedCatalog = Arc::new(RwLock::new(Catalog::load_or_install(&path).unwrap()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), BUNDLED_CATALOG);
+        assert_eq!(*shared.read().unwrap(), Catalog::bundled());
+
+        let _watcher = watch(&path, shared.clone()).unwrap();
+        let wait_for = |expected: usize| {
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while shared.read().unwrap().blinks.len() != expected && Instant::now() < deadline {
+                std::thread::sleep(Duration::from_millis(20));
+            }
+            shared.read().unwrap().blinks.len()
+        };
+
+        fs::write(&path, catalog_json(LENDING)).unwrap();
+        assert_eq!(wait_for(1), 1);
+        assert_eq!(shared.read().unwrap().blinks[0].id, "lend-v2");
+
+        // An invalid edit keeps the catalog that was loaded last
+        fs::write(&path, "{ not json").unwrap();
+        std::thread::sleep(Duration::from_millis(300));
+        assert_eq!(shared.read().unwrap().blinks.len(), 1);
+
+        // Replacing the file, as editors do, is picked up too
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, BUNDLED_CATALOG).unwrap();
+        fs::rename(&temp_path, &path).unwrap();
+        let bundled = Catalog::bundled().blinks.len();
+        assert_eq!(wait_for(bundled), bundled);
+    }
+}
@@ -0,0 +1,219 @@
+// Local ingestion checkpoint: the last visit read from each browser profile and
+// the rolling keyword counts built from every visit ingested so far.
+
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use url::Url;
+
+use crate::history::HistoryEntry;
+use crate::normalize::{self, SurfaceForms};
+use crate::scoring::{InterestScores, ScoringConfig};
+use crate::taxonomy::{self, CategoryScore};
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProfileCheckpoint {
+    pub last_visit_id: i64,
+    /// Unix seconds.
+    pub last_visit_time: i64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// Keyed by `ProfileInfo::id`.
+    pub profiles: HashMap<String, ProfileCheckpoint>,
+    /// Keyed by the normalised keyword.
+    pub keyword_counts: HashMap<String, u32>,
+    #[serde(default)]
+    pub surface_forms: SurfaceForms,
+    /// Distinct pages ingested so far; the corpus size for IDF.
+    #[serde(default)]
+    pub documents: u32,
+    /// Hashes of the URLs counted in `documents`, so a page seen again in a
+    /// later run is not counted twice.
+    #[serde(default)]
+    pub seen_urls: HashSet<u64>,
+    /// Number of pages each normalised keyword appeared on.
+    #[serde(default)]
+    pub document_frequencies: HashMap<String, u32>,
+    /// Keyed by category path, weighted by visits like `keyword_counts`.
+    #[serde(default)]
+    pub category_scores: HashMap<String, u32>,
+    /// Recency-weighted counterparts of `keyword_counts` and `category_scores`.
+    #[serde(default)]
+    pub scores: InterestScores,
+    /// Unix seconds of the last analysis run, for `TimeWindow::SinceLastRun`.
+    #[serde(default)]
+    pub last_run: Option<i64>,
+}
+
+impl Checkpoint {
+    pub fn default_path() -> PathBuf {
+        dirs::data_local_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("solfhe")
+            .join("checkpoint.json")
+    }
+
+    /// A missing file is an empty checkpoint (first run).
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(serde_json::from_str(&contents)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Written to a temporary file first so an interrupted run keeps the old checkpoint.
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, serde_json::to_string_pretty(self)?)?;
+        fs::rename(&temp_path, path)?;
+        Ok(())
+    }
+
+    pub fn after_visit_id(&self, profile_id: &str) -> i64 {
+        self.profiles.get(profile_id).map_or(0, |p| p.last_visit_id)
+    }
+
+    pub fn record(&mut self, profile_id: &str, entries: &[HistoryEntry]) {
+        let profile = self.profiles.entry(profile_id.to_string()).or_default();
+        for entry in entries {
+            profile.last_visit_id = profile.last_visit_id.max(entry.last_visit_id);
+            profile.last_visit_time = profile.last_visit_time.max(entry.last_visit);
+        }
+    }
+
+    /// Adds one page's keyword counts, weighted by how many new visits it had.
+    /// The page counts once towards the document frequencies however often it
+    /// was visited, in this run or any earlier one. Counts saturate rather than
+    /// wrap, as a heavily reloaded page can multiply them past `u32::MAX`.
+    pub fn merge_keywords(&mut self, url: &str, counts: &HashMap<String, u32>, visits: u32) {
+        let new_page = self.seen_urls.insert(url_hash(url));
+        if new_page {
+            self.documents = self.documents.saturating_add(1);
+        }
+        for (word, count) in counts {
+            let total = self.keyword_counts.entry(word.clone()).or_insert(0);
+            *total = total.saturating_add(count.saturating_mul(visits));
+            if new_page {
+                let frequency = self.document_frequencies.entry(word.clone()).or_insert(0);
+                *frequency = frequency.saturating_add(1);
+            }
+        }
+    }
+
+    pub fn merge_categories(&mut self, scores: &[CategoryScore], visits: u32) {
+        for score in scores {
+            let total = self.category_scores.entry(score.category.clone()).or_insert(0);
+            *total = total.saturating_add(score.score.saturating_mul(visits));
+        }
+    }
+
+    /// Folds one run's pages, each with the keywords found in its URL, into the
+    /// rolling profile at `now`: stored scores are aged first, then every page
+    /// adds its keywords, taxonomy categories and recency-weighted scores.
+    pub fn ingest<I>(&mut self, pages: I, now: i64, config: &ScoringConfig)
+    where
+        I: IntoIterator<Item = (HistoryEntry, HashMap<String, u32>, SurfaceForms)>,
+    {
+        self.scores.decay_to(now, config);
+        for (entry, keywords, surface_forms) in pages {
+            let host = Url::parse(&entry.url).ok().and_then(|url| url.host_str().map(str::to_string));
+            let categories = taxonomy::embedded().score(host.as_deref(), &keywords);
+            // Each new visit counts once towards the rolling profile
+            self.merge_keywords(&entry.url, &keywords, entry.new_visits);
+            self.merge_categories(&categories, entry.new_visits);
+            self.scores.add_page(&keywords, &categories, config.visit_weight(&entry, now));
+            normalize::merge_surface_forms(&mut self.surface_forms, &surface_forms);
+        }
+    }
+}
+
+/// The first 8 bytes of the URL's SHA-256; the checkpoint does not keep the URLs themselves.
+fn url_hash(url: &str) -> u64 {
+    let digest = Sha256::digest(url.as_bytes());
+    u64::from_le_bytes(digest[..8].try_into().expect("SHA-256 is 32 bytes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(last_visit_id: i64, last_visit: i64) -> HistoryEntry {
+        HistoryEntry {
+            url: "https://solana.com/".to_string(),
+            browser: "Chrome".to_string(),
+            profile: "Person 1".to_string(),
+            visit_count: 1,
+            typed_count: 0,
+            last_visit,
+            new_visits: 1,
+            last_visit_id,
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("checkpoint.json");
+        assert_eq!(Checkpoint::load(&path).unwrap(), Checkpoint::default());
+
+        let mut checkpoint = Checkpoint::default();
+        checkpoint.record("Chrome/Default", &[entry(7, 900), entry(12, 800)]);
+        checkpoint.record("Chrome/Default", &[]);
+        assert_eq!(checkpoint.after_visit_id("Chrome/Default"), 12);
+        assert_eq!(checkpoint.profiles["Chrome/Default"].last_visit_time, 900);
+        assert_eq!(checkpoint.after_visit_id("Firefox/work"), 0);
+
+        let counts = HashMap::from([("solana".to_string(), 2)]);
+        checkpoint.merge_keywords("https://solana.com/", &counts, 3);
+        checkpoint.merge_keywords("https://solana.com/staking", &counts, 1);
+        assert_eq!(checkpoint.keyword_counts["solana"], 8);
+        assert_eq!(checkpoint.documents, 2);
+        assert_eq!(checkpoint.document_frequencies["solana"], 2);
+        checkpoint.merge_categories(&[CategoryScore { category: "Crypto".to_string(), score: 2 }], 3);
+        assert_eq!(checkpoint.category_scores["Crypto"], 6);
+        crate::normalize::record_surface_form(&mut checkpoint.surface_forms, "token", "tokens");
+        checkpoint.last_run = Some(1_000);
+
+        checkpoint.save(&path).unwrap();
+        assert_eq!(Checkpoint::load(&path).unwrap(), checkpoint);
+
+        // A page reloaded often enough to overflow the counts saturates them
+        checkpoint.merge_keywords("https://solana.com/", &counts, u32::MAX);
+        assert_eq!(checkpoint.keyword_counts["solana"], u32::MAX);
+        checkpoint.merge_categories(&[CategoryScore { category: "Crypto".to_string(), score: 2 }], u32::MAX);
+        assert_eq!(checkpoint.category_scores["Crypto"], u32::MAX);
+    }
+
+    #[test]
+    fn test_same_url_in_two_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("checkpoint.json");
+        let counts = HashMap::from([("solana".to_string(), 1), ("stake".to_string(), 1)]);
+
+        let mut first_run = Checkpoint::default();
+        first_run.merge_keywords("https://solana.com/staking", &counts, 2);
+        first_run.save(&path).unwrap();
+
+        // New visits still add keyword weight, but the page is one document
+        let mut second_run = Checkpoint::load(&path).unwrap();
+        second_run.merge_keywords("https://solana.com/staking", &counts, 1);
+        assert_eq!(second_run.keyword_counts["solana"], 3);
+        assert_eq!(second_run.documents, 1);
+        assert_eq!(second_run.document_frequencies["solana"], 1);
+        assert_eq!(second_run.document_frequencies["stake"], 1);
+
+        second_run.merge_keywords("https://solana.com/", &counts, 1);
+        assert_eq!(second_run.documents, 2);
+        assert_eq!(second_run.document_frequencies["solana"], 2);
+    }
+}
@@ -0,0 +1,75 @@
+// Host name splitting with the Public Suffix List (data/public_suffix_list.dat,
+// from https://publicsuffix.org/list/). The suffix (`co.uk`, `github.io`, ...)
+// is never a keyword; the label in front of it is the site's brand.
+
+use std::sync::OnceLock;
+
+use publicsuffix::{List, Psl};
+
+static PUBLIC_SUFFIX_LIST: &str = include_str!("../data/public_suffix_list.dat");
+
+fn list() -> &'static List {
+    static LIST: OnceLock<List> = OnceLock::new();
+    LIST.get_or_init(|| {
+        PUBLIC_SUFFIX_LIST
+            .parse()
+            .expect("embedded public suffix list is valid")
+    })
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct HostParts {
+    /// Labels in front of the registrable domain, left to right.
+    pub subdomains: Vec<String>,
+    /// The registrable domain without its suffix, e.g. `bbc` for `bbc.co.uk`.
+    pub brand: Option<String>,
+    pub suffix: Option<String>,
+}
+
+pub fn split_host(host: &str) -> HostParts {
+    let host = host.trim_end_matches('.').to_lowercase();
+    let Some(suffix) = list().suffix(host.as_bytes()) else {
+        return HostParts::default();
+    };
+    let suffix = String::from_utf8_lossy(suffix.as_bytes()).into_owned();
+
+    // The host is itself a public suffix (e.g. `github.io`): nothing is registrable
+    let Some(rest) = host.strip_suffix(&suffix).and_then(|r| r.strip_suffix('.')) else {
+        return HostParts { suffix: Some(suffix), ..HostParts::default() };
+    };
+
+    let mut labels: Vec<String> = rest.split('.').filter(|l| !l.is_empty()).map(str::to_string).collect();
+    let brand = labels.pop();
+    HostParts {
+        subdomains: labels,
+        brand,
+        suffix: Some(suffix),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parts(subdomains: &[&str], brand: &str, suffix: &str) -> HostParts {
+        HostParts {
+            subdomains: subdomains.iter().map(|s| s.to_string()).collect(),
+            brand: Some(brand.to_string()),
+            suffix: Some(suffix.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_split_host() {
+        assert_eq!(split_host("www.bbc.co.uk"), parts(&["www"], "bbc", "co.uk"));
+        assert_eq!(split_host("solana-labs.github.io"), parts(&[], "solana-labs", "github.io"));
+        assert_eq!(split_host("docs.solana.com"), parts(&["docs"], "solana", "com"));
+        assert_eq!(split_host("News.YCombinator.com."), parts(&["news"], "ycombinator", "com"));
+        assert_eq!(split_host("a.b.example.xyz"), parts(&["a", "b"], "example", "xyz"));
+
+        let suffix_only = split_host("github.io");
+        assert_eq!(suffix_only.brand, None);
+        assert!(suffix_only.subdomains.is_empty());
+        assert_eq!(split_host(""), HostParts::default());
+    }
+}
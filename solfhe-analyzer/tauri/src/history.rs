@@ -0,0 +1,732 @@
+// Browser history sources. Every backend reads a snapshot of the browser's
+// database (the browser keeps it locked) and returns visited URLs, most
+// recent first, tagged with the browser and profile they came from.
+
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::checkpoint::Checkpoint;
+use crate::snapshot::Snapshot;
+
+/// Seconds between the WebKit/Chrome epoch (1601-01-01) and the Unix epoch.
+pub const WEBKIT_EPOCH_OFFSET_SECS: i64 = 11_644_473_600;
+const SECS_PER_DAY: i64 = 86_400;
+
+// Every query takes (after visit id, since, min visit count, max count), with
+// `since` in the browser's own time unit, and returns one row per page:
+// (url, visit count, typed count, last visit, new visits, last visit id).
+// Only visits after the checkpointed visit id are read, and typed counts cover
+// the same visits rather than the page's lifetime.
+//
+// Visits are read in id order and stop just before the visit that would bring
+// in page `max count + 1`, so every visit up to the highest id returned has
+// been read and the checkpoint never skips a page that did not fit.
+//
+// A transition whose core type (the low byte) is 1 is a typed visit
+const CHROME_URL_QUERY: &str = "
+    WITH new_visits AS (
+        SELECT v.id, v.url AS page, v.visit_time, v.transition,
+               ROW_NUMBER() OVER (PARTITION BY v.url ORDER BY v.id) = 1 AS first_visit
+        FROM visits v
+        JOIN urls u ON u.id = v.url
+        WHERE v.id > ?1 AND v.visit_time >= ?2 AND u.visit_count >= ?3
+    ), numbered AS (
+        SELECT *, SUM(first_visit) OVER (ORDER BY id) AS pages FROM new_visits
+    )
+    SELECT u.url, u.visit_count, SUM((n.transition & 255) = 1), MAX(n.visit_time), COUNT(n.id), MAX(n.id)
+    FROM numbered n
+    JOIN urls u ON u.id = n.page
+    WHERE n.pages <= ?4
+    GROUP BY u.id
+    ORDER BY MAX(n.visit_time) DESC";
+
+// visit_type 2 is a typed visit
+const FIREFOX_URL_QUERY: &str = "
+    WITH new_visits AS (
+        SELECT v.id, v.place_id AS page, v.visit_date, v.visit_type,
+               ROW_NUMBER() OVER (PARTITION BY v.place_id ORDER BY v.id) = 1 AS first_visit
+        FROM moz_historyvisits v
+        JOIN moz_places p ON p.id = v.place_id
+        WHERE v.id > ?1 AND v.visit_date >= ?2 AND p.visit_count >= ?3
+    ), numbered AS (
+        SELECT *, SUM(first_visit) OVER (ORDER BY id) AS pages FROM new_visits
+    )
+    SELECT p.url, p.visit_count, SUM(n.visit_type = 2), MAX(n.visit_date), COUNT(n.id), MAX(n.id)
+    FROM numbered n
+    JOIN moz_places p ON p.id = n.page
+    WHERE n.pages <= ?4
+    GROUP BY p.id
+    ORDER BY MAX(n.visit_date) DESC";
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct HistoryEntry {
+    pub url: String,
+    pub browser: String,
+    pub profile: String,
+    pub visit_count: u32,
+    pub typed_count: u32,
+    /// Unix seconds.
+    pub last_visit: i64,
+    /// Visits to this page since the profile's checkpoint.
+    pub new_visits: u32,
+    pub last_visit_id: i64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeWindow {
+    All,
+    LastDays(u32),
+    SinceLastRun,
+}
+
+/// Extraction settings as the frontend sends them.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ExtractOptions {
+    pub window: TimeWindow,
+    pub max_count: usize,
+    pub min_visit_count: u32,
+}
+
+impl Default for ExtractOptions {
+    fn default() -> Self {
+        Self {
+            window: TimeWindow::LastDays(30),
+            max_count: 200,
+            min_visit_count: 1,
+        }
+    }
+}
+
+impl ExtractOptions {
+    /// `SinceLastRun` falls back to everything when there was no previous run.
+    pub fn resolve(&self, last_run: Option<i64>, now: i64) -> HistoryQuery {
+        let since = match self.window {
+            TimeWindow::All => None,
+            TimeWindow::LastDays(days) => Some(now - i64::from(days) * SECS_PER_DAY),
+            TimeWindow::SinceLastRun => last_run,
+        };
+        HistoryQuery {
+            since,
+            max_count: self.max_count,
+            min_visit_count: self.min_visit_count,
+            after_visit_id: 0,
+        }
+    }
+}
+
+/// What a `HistorySource` reads; `since` is in Unix seconds. New visits are read
+/// oldest first; when they span more than `max_count` pages, the rest are left
+/// past the checkpoint for the next run.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HistoryQuery {
+    pub since: Option<i64>,
+    pub max_count: usize,
+    pub min_visit_count: u32,
+    pub after_visit_id: i64,
+}
+
+pub fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+pub fn webkit_to_unix(micros: i64) -> i64 {
+    micros / 1_000_000 - WEBKIT_EPOCH_OFFSET_SECS
+}
+
+pub fn unix_to_webkit(secs: i64) -> i64 {
+    (secs + WEBKIT_EPOCH_OFFSET_SECS) * 1_000_000
+}
+
+struct VisitRow {
+    url: String,
+    visit_count: u32,
+    typed_count: u32,
+    last_visit: i64,
+    new_visits: u32,
+    last_visit_id: i64,
+}
+
+/// A browser profile the user can select; `id` is `"<browser>/<profile dir>"`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ProfileInfo {
+    pub id: String,
+    pub browser: String,
+    pub profile: String,
+}
+
+pub trait HistorySource {
+    fn profile(&self) -> ProfileInfo;
+    fn extract_urls(&self, query: &HistoryQuery) -> Result<Vec<HistoryEntry>, Box<dyn Error>>;
+}
+
+/// `to_unix` converts the browser's timestamps to Unix seconds.
+fn tag_rows(rows: Vec<VisitRow>, profile: &ProfileInfo, to_unix: fn(i64) -> i64) -> Vec<HistoryEntry> {
+    rows.into_iter()
+        .map(|row| HistoryEntry {
+            url: row.url,
+            browser: profile.browser.clone(),
+            profile: profile.profile.clone(),
+            visit_count: row.visit_count,
+            typed_count: row.typed_count,
+            last_visit: to_unix(row.last_visit),
+            new_visits: row.new_visits,
+            last_visit_id: row.last_visit_id,
+        })
+        .collect()
+}
+
+/// One profile of a Chromium-based browser (Chrome, Chromium, Brave, Edge, Vivaldi).
+pub struct ChromiumHistory {
+    pub browser: String,
+    pub profile_dir: String,
+    pub profile_name: String,
+    pub history_path: PathBuf,
+}
+
+impl ChromiumHistory {
+    /// Every profile with a `History` file, across all installed Chromium browsers.
+    pub fn discover() -> Vec<Self> {
+        let Some(home) = dirs::home_dir() else {
+            return Vec::new();
+        };
+        let mut sources = Vec::new();
+        for (browser, user_data_dir) in chromium_user_data_dirs(&home) {
+            let profiles = match chromium_profiles(&user_data_dir) {
+                Ok(profiles) => profiles,
+                Err(e) => {
+                    println!("Skipping {}: {}", user_data_dir.display(), e);
+                    continue;
+                }
+            };
+            for (profile_dir, profile_name) in profiles {
+                let history_path = user_data_dir.join(&profile_dir).join("History");
+                if history_path.exists() {
+                    sources.push(Self {
+                        browser: browser.to_string(),
+                        profile_dir,
+                        profile_name,
+                        history_path,
+                    });
+                }
+            }
+        }
+        sources
+    }
+}
+
+impl HistorySource for ChromiumHistory {
+    fn profile(&self) -> ProfileInfo {
+        ProfileInfo {
+            id: format!("{}/{}", self.browser, self.profile_dir),
+            browser: self.browser.clone(),
+            profile: self.profile_name.clone(),
+        }
+    }
+
+    fn extract_urls(&self, query: &HistoryQuery) -> Result<Vec<HistoryEntry>, Box<dyn Error>> {
+        let since = query.since.map_or(0, unix_to_webkit);
+        let rows = query_snapshot(&self.history_path, CHROME_URL_QUERY, since, query)?;
+        Ok(tag_rows(rows, &self.profile(), webkit_to_unix))
+    }
+}
+
+fn chromium_user_data_dirs(home: &Path) -> Vec<(&'static str, PathBuf)> {
+    let browsers: [(&str, &str, &str, &str); 5] = [
+        // (name, Windows under AppData\Local, macOS under Application Support, Linux under .config)
+        ("Chrome", r"Google\Chrome\User Data", "Google/Chrome", "google-chrome"),
+        ("Chromium", r"Chromium\User Data", "Chromium", "chromium"),
+        ("Brave", r"BraveSoftware\Brave-Browser\User Data", "BraveSoftware/Brave-Browser", "BraveSoftware/Brave-Browser"),
+        ("Edge", r"Microsoft\Edge\User Data", "Microsoft Edge", "microsoft-edge"),
+        ("Vivaldi", r"Vivaldi\User Data", "Vivaldi", "vivaldi"),
+    ];
+    browsers
+        .iter()
+        .map(|&(name, windows, macos, linux)| {
+            let dir = if cfg!(target_os = "windows") {
+                home.join(r"AppData\Local").join(windows)
+            } else if cfg!(target_os = "macos") {
+                home.join("Library/Application Support").join(macos)
+            } else {
+                home.join(".config").join(linux)
+            };
+            (name, dir)
+        })
+        .filter(|(_, dir)| dir.is_dir())
+        .collect()
+}
+
+/// `(directory, display name)` for each profile, read from `Local State`. When
+/// that file is missing or unreadable, `Default` and `Profile N` directories are used.
+fn chromium_profiles(user_data_dir: &Path) -> std::io::Result<Vec<(String, String)>> {
+    let from_local_state = fs::read_to_string(user_data_dir.join("Local State"))
+        .ok()
+        .map(|contents| parse_local_state(&contents))
+        .unwrap_or_default();
+    if !from_local_state.is_empty() {
+        return Ok(from_local_state);
+    }
+
+    let entries = fs::read_dir(user_data_dir)?.collect::<Result<Vec<_>, _>>()?;
+    let mut profiles: Vec<(String, String)> = entries
+        .into_iter()
+        .filter_map(|e| e.file_name().into_string().ok())
+        .filter(|name| name == "Default" || name.starts_with("Profile "))
+        .map(|name| (name.clone(), name))
+        .collect();
+    profiles.sort();
+    Ok(profiles)
+}
+
+fn parse_local_state(contents: &str) -> Vec<(String, String)> {
+    let Ok(state) = serde_json::from_str::<serde_json::Value>(contents) else {
+        return Vec::new();
+    };
+    let Some(info_cache) = state["profile"]["info_cache"].as_object() else {
+        return Vec::new();
+    };
+    let mut profiles: Vec<(String, String)> = info_cache
+        .iter()
+        .map(|(dir, info)| {
+            let name = info["name"].as_str().unwrap_or(dir).to_string();
+            (dir.clone(), name)
+        })
+        .collect();
+    profiles.sort();
+    profiles
+}
+
+pub struct FirefoxHistory {
+    pub profile_name: String,
+    pub profile_dir: PathBuf,
+}
+
+impl FirefoxHistory {
+    /// Every profile listed in `profiles.ini` that has a `places.sqlite`.
+    pub fn discover() -> Vec<Self> {
+        let Some(root) = firefox_root() else {
+            return Vec::new();
+        };
+        let Ok(ini) = fs::read_to_string(root.join("profiles.ini")) else {
+            return Vec::new();
+        };
+        parse_profiles_ini(&ini, &root)
+            .into_iter()
+            .filter(|(_, dir)| dir.join("places.sqlite").exists())
+            .map(|(profile_name, profile_dir)| Self { profile_name, profile_dir })
+            .collect()
+    }
+}
+
+impl HistorySource for FirefoxHistory {
+    fn profile(&self) -> ProfileInfo {
+        ProfileInfo {
+            id: format!("Firefox/{}", self.profile_name),
+            browser: "Firefox".to_string(),
+            profile: self.profile_name.clone(),
+        }
+    }
+
+    fn extract_urls(&self, query: &HistoryQuery) -> Result<Vec<HistoryEntry>, Box<dyn Error>> {
+        // Firefox stores microseconds since the Unix epoch
+        let since = query.since.map_or(0, |secs| secs * 1_000_000);
+        let places = self.profile_dir.join("places.sqlite");
+        let rows = query_snapshot(&places, FIREFOX_URL_QUERY, since, query)?;
+        Ok(tag_rows(rows, &self.profile(), |micros| micros / 1_000_000))
+    }
+}
+
+fn firefox_root() -> Option<PathBuf> {
+    let home = dirs::home_dir()?;
+    Some(if cfg!(target_os = "windows") {
+        home.join(r"AppData\Roaming\Mozilla\Firefox")
+    } else if cfg!(target_os = "macos") {
+        home.join("Library/Application Support/Firefox")
+    } else {
+        home.join(".mozilla/firefox")
+    })
+}
+
+/// `(name, directory)` of each profile in `profiles.ini`, default profile first (the
+/// `[Install…]` default, then the legacy `Default=1` flag). `Path` is relative
+/// to the Firefox root unless `IsRelative=0`.
+fn parse_profiles_ini(contents: &str, root: &Path) -> Vec<(String, PathBuf)> {
+    struct Profile {
+        name: Option<String>,
+        path: Option<String>,
+        is_relative: bool,
+        is_default: bool,
+    }
+
+    enum Section {
+        Profile,
+        Install,
+        Other,
+    }
+
+    let mut profiles: Vec<Profile> = Vec::new();
+    let mut install_default = None;
+    let mut section = Section::Other;
+    for line in contents.lines().map(str::trim) {
+        if line.starts_with('[') {
+            section = if line.starts_with("[Profile") {
+                profiles.push(Profile { name: None, path: None, is_relative: true, is_default: false });
+                Section::Profile
+            } else if line.starts_with("[Install") {
+                Section::Install
+            } else {
+                Section::Other
+            };
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+        match (&section, profiles.last_mut()) {
+            (Section::Install, _) if key == "Default" => {
+                install_default.get_or_insert_with(|| value.to_string());
+            }
+            (Section::Profile, Some(profile)) => match key {
+                "Name" => profile.name = Some(value.to_string()),
+                "Path" => profile.path = Some(value.to_string()),
+                "IsRelative" => profile.is_relative = value != "0",
+                "Default" => profile.is_default = value == "1",
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    profiles.sort_by_key(|p| (p.path != install_default, !p.is_default));
+    profiles
+        .into_iter()
+        .filter_map(|p| {
+            let path = p.path?;
+            let dir = if p.is_relative { root.join(&path) } else { PathBuf::from(&path) };
+            Some((p.name.unwrap_or(path), dir))
+        })
+        .collect()
+}
+
+/// Runs `sql` on a snapshot of the database, see `Snapshot`.
+fn query_snapshot(
+    db_path: &Path,
+    sql: &str,
+    since: i64,
+    query: &HistoryQuery,
+) -> Result<Vec<VisitRow>, Box<dyn Error>> {
+    let snapshot = Snapshot::create(db_path)?;
+    let conn = snapshot.open()?;
+    let mut stmt = conn.prepare(sql)?;
+    let params = (query.after_visit_id, since, query.min_visit_count, query.max_count as i64);
+    let rows = stmt
+        .query_map(params, |row| {
+            Ok(VisitRow {
+                url: row.get(0)?,
+                visit_count: row.get(1)?,
+                typed_count: row.get(2)?,
+                last_visit: row.get(3)?,
+                new_visits: row.get(4)?,
+                last_visit_id: row.get(5)?,
+            })
+        })?
+        .collect::<Result<_, _>>()?;
+    Ok(rows)
+}
+
+pub fn discover_sources() -> Vec<Box<dyn HistorySource + Send>> {
+    let mut sources: Vec<Box<dyn HistorySource + Send>> = Vec::new();
+    for chromium in ChromiumHistory::discover() {
+        sources.push(Box::new(chromium));
+    }
+    for firefox in FirefoxHistory::discover() {
+        sources.push(Box::new(firefox));
+    }
+    sources
+}
+
+pub fn list_profiles() -> Vec<ProfileInfo> {
+    discover_sources().iter().map(|s| s.profile()).collect()
+}
+
+/// New URLs from the selected profiles (all of them when `selected` is `None`),
+/// read after each profile's checkpoint, which is then advanced. A source that
+/// fails is skipped and keeps its checkpoint.
+pub fn extract_links(
+    selected: Option<&[String]>,
+    query: &HistoryQuery,
+    checkpoint: &mut Checkpoint,
+) -> Result<Vec<HistoryEntry>, Box<dyn Error>> {
+    let sources: Vec<_> = discover_sources()
+        .into_iter()
+        .filter(|s| match selected {
+            Some(ids) => ids.contains(&s.profile().id),
+            None => true,
+        })
+        .collect();
+    if sources.is_empty() {
+        return Err("No browser history found".into());
+    }
+
+    let mut entries = Vec::new();
+    for source in sources {
+        let profile_id = source.profile().id;
+        let query = HistoryQuery {
+            after_visit_id: checkpoint.after_visit_id(&profile_id),
+            ..*query
+        };
+        match source.extract_urls(&query) {
+            Ok(found) => {
+                checkpoint.record(&profile_id, &found);
+                entries.extend(found);
+            }
+            Err(e) => println!("Skipping {}: {}", profile_id, e),
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    #[test]
+    fn test_parse_profiles_ini() {
+        let ini = "\
+[Install4F96D1932A9F858E]
+Default=Profiles/abcd.default-release
+Locked=1
+
+[Profile1]
+Name=default
+IsRelative=1
+Path=Profiles/xyz.default
+Default=1
+
+[Profile0]
+Name=default-release
+IsRelative=1
+Path=Profiles/abcd.default-release
+
+[Profile2]
+Name=work
+IsRelative=0
+Path=/data/firefox/work
+";
+        let root = Path::new("/home/u/.mozilla/firefox");
+        assert_eq!(
+            parse_profiles_ini(ini, root),
+            vec![
+                ("default-release".to_string(), root.join("Profiles/abcd.default-release")),
+                ("default".to_string(), root.join("Profiles/xyz.default")),
+                ("work".to_string(), PathBuf::from("/data/firefox/work")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_local_state() {
+        let local_state = r#"{
+            "profile": {
+                "info_cache": {
+                    "Profile 1": { "name": "Work" },
+                    "Default": { "name": "Person 1" },
+                    "Profile 3": {}
+                }
+            }
+        }"#;
+        assert_eq!(
+            parse_local_state(local_state),
+            vec![
+                ("Default".to_string(), "Person 1".to_string()),
+                ("Profile 1".to_string(), "Work".to_string()),
+                ("Profile 3".to_string(), "Profile 3".to_string()),
+            ]
+        );
+        assert!(parse_local_state("not json").is_empty());
+    }
+
+    fn query(since: Option<i64>, max_count: usize, min_visit_count: u32) -> HistoryQuery {
+        HistoryQuery { since, max_count, min_visit_count, after_visit_id: 0 }
+    }
+
+    #[test]
+    fn test_time_windows() {
+        let now = 1_700_000_000;
+        assert_eq!(unix_to_webkit(0), WEBKIT_EPOCH_OFFSET_SECS * 1_000_000);
+        assert_eq!(webkit_to_unix(unix_to_webkit(now)), now);
+        // 2023-11-14 22:13:20 UTC as Chrome stores it
+        assert_eq!(webkit_to_unix(13_344_473_600_000_000), now);
+
+        let mut options = ExtractOptions::default();
+        assert_eq!(options.resolve(None, now).since, Some(now - 30 * SECS_PER_DAY));
+        options.window = TimeWindow::SinceLastRun;
+        assert_eq!(options.resolve(Some(now - 60), now).since, Some(now - 60));
+        assert_eq!(options.resolve(None, now).since, None);
+
+        let parsed: ExtractOptions =
+            serde_json::from_str(r#"{"window": {"last_days": 7}, "min_visit_count": 2}"#).unwrap();
+        assert_eq!(parsed.window, TimeWindow::LastDays(7));
+        assert_eq!(parsed.min_visit_count, 2);
+        assert_eq!(parsed.max_count, ExtractOptions::default().max_count);
+    }
+
+    /// Visits 1-4: old.example, solana.com twice (both typed), once.example.
+    fn chrome_fixture(dir: &Path) -> ChromiumHistory {
+        let history_path = dir.join("History");
+        let (old, recent, newest) = (unix_to_webkit(1_000), unix_to_webkit(5_000), unix_to_webkit(9_000));
+        {
+            let conn = Connection::open(&history_path).unwrap();
+            conn.execute_batch(&format!(
+                "CREATE TABLE urls (id INTEGER PRIMARY KEY, url TEXT, visit_count INTEGER, typed_count INTEGER, last_visit_time INTEGER);
+                 CREATE TABLE visits (id INTEGER PRIMARY KEY, url INTEGER, visit_time INTEGER, transition INTEGER);
+                 INSERT INTO urls VALUES
+                     (1, 'https://old.example/', 9, 0, {old}),
+                     (2, 'https://solana.com/', 4, 3, {recent}),
+                     (3, 'https://once.example/', 1, 0, {newest});
+                 INSERT INTO visits (url, visit_time, transition) VALUES
+                     (1, {old}, 0), (2, {old}, 1), (2, {recent}, 805306369), (3, {newest}, 8);"
+            ))
+            .unwrap();
+        }
+
+        ChromiumHistory {
+            browser: "Brave".to_string(),
+            profile_dir: "Default".to_string(),
+            profile_name: "Person 1".to_string(),
+            history_path,
+        }
+    }
+
+    #[test]
+    fn test_chrome_query() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = chrome_fixture(dir.path());
+        let entries = source.extract_urls(&query(Some(4_000), 10, 2)).unwrap();
+        assert_eq!(
+            entries,
+            vec![HistoryEntry {
+                url: "https://solana.com/".to_string(),
+                browser: "Brave".to_string(),
+                profile: "Person 1".to_string(),
+                visit_count: 4,
+                typed_count: 1,
+                last_visit: 5_000,
+                new_visits: 1,
+                last_visit_id: 3,
+            }]
+        );
+        // once.example's visit would be the third page, so it is left for the next run
+        let all = source.extract_urls(&query(None, 2, 1)).unwrap();
+        let urls: Vec<&str> = all.iter().map(|e| e.url.as_str()).collect();
+        assert_eq!(urls, vec!["https://solana.com/", "https://old.example/"]);
+        assert_eq!(all[0].new_visits, 2);
+        assert_eq!(all[0].typed_count, 2);
+
+        // Only visits after the checkpoint are read
+        let mut checkpoint = Checkpoint::default();
+        checkpoint.record("Brave/Default", &all);
+        let after = checkpoint.after_visit_id("Brave/Default");
+        assert_eq!(after, 3);
+        let incremental = HistoryQuery { after_visit_id: 2, ..query(None, 10, 1) };
+        let urls: Vec<String> = source.extract_urls(&incremental).unwrap().into_iter().map(|e| e.url).collect();
+        assert_eq!(urls, vec!["https://once.example/", "https://solana.com/"]);
+        let next = source.extract_urls(&HistoryQuery { after_visit_id: after, ..incremental }).unwrap();
+        assert_eq!(next.len(), 1);
+        assert_eq!(next[0].url, "https://once.example/");
+        checkpoint.record("Brave/Default", &next);
+        assert!(source
+            .extract_urls(&HistoryQuery { after_visit_id: checkpoint.after_visit_id("Brave/Default"), ..incremental })
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_max_count_below_new_visits() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = chrome_fixture(dir.path());
+
+        // One page per run: each run resumes where the previous one stopped
+        let mut checkpoint = Checkpoint::default();
+        let mut runs = Vec::new();
+        loop {
+            let after_visit_id = checkpoint.after_visit_id("Brave/Default");
+            let found = source.extract_urls(&HistoryQuery { after_visit_id, ..query(None, 1, 1) }).unwrap();
+            if found.is_empty() {
+                break;
+            }
+            checkpoint.record("Brave/Default", &found);
+            runs.push(found);
+        }
+        let pages: Vec<Vec<(&str, u32)>> = runs
+            .iter()
+            .map(|run| run.iter().map(|e| (e.url.as_str(), e.new_visits)).collect())
+            .collect();
+        assert_eq!(
+            pages,
+            vec![
+                vec![("https://old.example/", 1)],
+                vec![("https://solana.com/", 2)],
+                vec![("https://once.example/", 1)],
+            ]
+        );
+        assert_eq!(checkpoint.after_visit_id("Brave/Default"), 4);
+    }
+
+    #[test]
+    fn test_firefox_query() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("places.sqlite");
+        {
+            let conn = Connection::open(&db_path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE moz_places (id INTEGER PRIMARY KEY, url TEXT, visit_count INTEGER);
+                 CREATE TABLE moz_historyvisits (id INTEGER PRIMARY KEY, place_id INTEGER, visit_date INTEGER, visit_type INTEGER);
+                 INSERT INTO moz_places VALUES (1, 'https://solana.com/', 2), (2, 'https://example.com/a', 1), (3, 'https://never.visited/', 0);
+                 INSERT INTO moz_historyvisits (place_id, visit_date, visit_type) VALUES
+                     (1, 100000000, 2), (2, 200000000, 1), (1, 300000000, 1);",
+            )
+            .unwrap();
+        }
+
+        let source = FirefoxHistory { profile_name: "work".to_string(), profile_dir: dir.path().to_path_buf() };
+        let urls: Vec<String> = source
+            .extract_urls(&query(None, 10, 1))
+            .unwrap()
+            .into_iter()
+            .map(|e| e.url)
+            .collect();
+        assert_eq!(urls, vec!["https://solana.com/", "https://example.com/a"]);
+
+        // With room for one page, reading stops before example.com's visit
+        let first = source.extract_urls(&query(None, 1, 1)).unwrap();
+        assert_eq!(
+            first,
+            vec![HistoryEntry {
+                url: "https://solana.com/".to_string(),
+                browser: "Firefox".to_string(),
+                profile: "work".to_string(),
+                visit_count: 2,
+                typed_count: 1,
+                last_visit: 100,
+                new_visits: 1,
+                last_visit_id: 1,
+            }]
+        );
+        // Only the visit at 300 s is in the window, so the page has no typed visit there
+        let windowed = source.extract_urls(&query(Some(250), 10, 1)).unwrap();
+        assert_eq!(windowed.len(), 1);
+        assert_eq!(windowed[0].typed_count, 0);
+        assert!(source.extract_urls(&query(None, 10, 3)).unwrap().is_empty());
+    }
+}
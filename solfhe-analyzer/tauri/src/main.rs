@@ -1,46 +1,86 @@
-use std::sync::Arc;
+use std::sync::{Arc, PoisonError, RwLock};
 use tokio::sync::Mutex;
 use tauri::{Manager, Runtime};
 use serde::{Serialize, Deserialize};
 use std::collections::{HashMap, HashSet};
-use std::fs;
 use std::path::PathBuf;
 use std::time::Duration;
-use rusqlite::Connection;
 use url::Url;
 use sha2::{Sha256, Digest};
 use base64::{Engine as _, engine::general_purpose};
 use solana_sdk::{
     signature::{Keypair, Signer, Signature},
     transaction::Transaction,
-    system_instruction,
     pubkey::Pubkey,
 };
 use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcSimulateTransactionConfig;
+use solana_client::rpc_response::RpcSimulateTransactionResult;
 use solana_transaction_status::UiTransactionEncoding;
 use spl_memo;
 use std::fs::File;
 use std::io::Write;
-use std::process::Command;
 
-const BLOCKCHAIN_NETWORKS: [&str; 20] = [
-    "bitcoin", "ethereum", "scroll", "polkadot", "solana", "zk-lokomotive", "cosmos",
-    "algorand", "mina", "chainlink", "superteam", "aave", "compound", "maker",
-    "polygon", "binance", "tron", "wormhole", "stellar", "filecoin"
-];
-
-const IGNORED_WORDS: [&str; 18] = [
-    "http", "https", "www", "com", "org", "net", "search", "google", "?", "q", "=", "xyz", "&", "%", "#", "oq", "://", ":UTF-8"
+mod actions;
+mod catalog;
+mod checkpoint;
+mod domain;
+mod history;
+mod matcher;
+mod normalize;
+mod profile;
+mod scoring;
+mod search;
+mod snapshot;
+mod taxonomy;
+#[path = "../../src/tokenizer.rs"]
+mod tokenizer;
+
+use checkpoint::Checkpoint;
+
+// Public suffixes (com, co.uk, github.io, ...) are dropped by `domain::split_host`
+const IGNORED_WORDS: [&str; 11] = [
+    "http", "https", "www", "?", "=", "&", "%", "#", "oq", "://", ":UTF-8"
 ];
 
 #[derive(Clone, Serialize, Deserialize)]
 struct AnalysisResult {
-    most_common_word: String,
-    count: u32,
+    #[serde(flatten)]
+    profile: profile::InterestProfile,
+    /// Blinks ranked by similarity to the profile, best first.
+    #[serde(default)]
+    matches: Vec<matcher::Match>,
+    /// The best match's action, as the UI renders it.
+    #[serde(default)]
+    preview: Option<actions::ActionPreview>,
     compressed_data: String,
     transaction_signature: Option<String>,
 }
 
+/// What the user is shown, and has to confirm, before an action's transaction
+/// is signed.
+#[derive(Clone, Serialize)]
+struct PreparedAction {
+    /// Hash of the transaction's message; `confirm_action` signs only this one.
+    id: String,
+    fee_payer: String,
+    message: Option<String>,
+    /// From simulating the transaction against the cluster.
+    units_consumed: Option<u64>,
+    logs: Vec<String>,
+}
+
+struct PendingAction {
+    transaction: actions::PendingTransaction,
+    message: Option<String>,
+}
+
+#[derive(Clone, Serialize)]
+struct ActionOutcome {
+    signature: String,
+    message: Option<String>,
+}
+
 #[derive(Clone, Serialize)]
 struct ProgressPayload {
     message: String,
@@ -49,95 +89,144 @@ struct ProgressPayload {
 
 struct AnalysisState {
     is_running: bool,
-    results: Vec<AnalysisResult>,
+    result: Option<AnalysisResult>,
     client: RpcClient,
     account1: Keypair,
-    account2: Keypair,
+    checkpoint_path: PathBuf,
+    catalog: catalog::SharedCatalog,
+    actions: actions::ActionsClient,
+    /// The last prepared action, until it is confirmed or cancelled.
+    pending_action: Option<PendingAction>,
 }
 
 #[tauri::command]
 async fn start_analysis<R: Runtime>(
     window: tauri::Window<R>,
     state: tauri::State<'_, Arc<Mutex<AnalysisState>>>,
+    profiles: Option<Vec<String>>,
+    options: Option<history::ExtractOptions>,
+    scoring: Option<scoring::ScoringConfig>,
 ) -> Result<(), String> {
-    let mut analysis_state = state.lock().await;
-    analysis_state.is_running = true;
+    let scoring = scoring.unwrap_or_default();
+    state.lock().await.is_running = true;
     
     let steps = [
-        "Extracting Chrome history",
+        "Extracting browser history",
         "Analyzing keywords",
         "Applying ZK compression",
         "Interacting with Solana blockchain",
+        "Matching blinks",
         "Storing results",
-        "Running Python script",
     ];
     
     for (i, step) in steps.iter().enumerate() {
-        if !analysis_state.is_running {
-            break;
-        }
+        // The state stays locked only while a step works on it, never across
+        // an await, so stopping or reading results is not held up by a run
+        let mut best_match = None;
+        {
+            let mut analysis_state = state.lock().await;
+            if !analysis_state.is_running {
+                break;
+            }
         
-        let progress = ProgressPayload {
-            message: step.to_string(),
-            percentage: (i as f32 + 1.0) / steps.len() as f32,
-        };
+            let progress = ProgressPayload {
+                message: step.to_string(),
+                percentage: (i as f32 + 1.0) / steps.len() as f32,
+            };
         
-        window.emit("analysis_progress", &progress).map_err(|e| e.to_string())?;
+            window.emit("analysis_progress", &progress).map_err(|e| e.to_string())?;
         
-        match i {
-            0 => {
-                let urls = extract_links_from_chrome().map_err(|e| e.to_string())?;
-                analysis_state.results = Vec::new();
-                for url in urls {
-                    let mut word_counter = HashMap::new();
-                    analyze_link(&url, &mut word_counter);
-                    if let Some((word, count)) = get_most_common_word(&word_counter) {
-                        analysis_state.results.push(AnalysisResult {
-                            most_common_word: word,
-                            count,
-                            compressed_data: String::new(),
-                            transaction_signature: None,
-                        });
+            match i {
+                0 => {
+                    let now = history::unix_now();
+                    let mut checkpoint = Checkpoint::load(&analysis_state.checkpoint_path).map_err(|e| e.to_string())?;
+                    let query = options.clone().unwrap_or_default().resolve(checkpoint.last_run, now);
+                    let entries = history::extract_links(profiles.as_deref(), &query, &mut checkpoint)
+                        .map_err(|e| e.to_string())?;
+                    checkpoint.last_run = Some(now);
+                    let pages = entries.into_iter().map(|entry| {
+                        let mut word_counter = HashMap::new();
+                        let mut surface_forms = normalize::SurfaceForms::new();
+                        analyze_link(&entry.url, &mut word_counter, &mut surface_forms);
+                        (entry, word_counter, surface_forms)
+                    });
+                    checkpoint.ingest(pages, now, &scoring);
+                    checkpoint.save(&analysis_state.checkpoint_path).map_err(|e| e.to_string())?;
+                },
+                1 => {
+                    // The profile covers every run so far, so it is built from the saved checkpoint
+                    let checkpoint = Checkpoint::load(&analysis_state.checkpoint_path).map_err(|e| e.to_string())?;
+                    analysis_state.result = Some(AnalysisResult {
+                        profile: profile::InterestProfile::from_checkpoint(&checkpoint, profile::TOP_KEYWORDS, history::unix_now(), &scoring),
+                        matches: Vec::new(),
+                        preview: None,
+                        compressed_data: String::new(),
+                        transaction_signature: None,
+                    });
+                },
+                2 => {
+                    if let Some(result) = analysis_state.result.as_mut() {
+                        let json_string = serde_json::to_string(&result.profile).map_err(|e| e.to_string())?;
+                        result.compressed_data = zk_compress(&json_string);
+                    }
+                },
+                3 => {
+                    let AnalysisState { result, client, account1, .. } = &mut *analysis_state;
+                    if let Some(result) = result.as_mut() {
+                        match transfer_compressed_hash(
+                            client,
+                            account1,
+                            &result.compressed_data,
+                        ) {
+                            Ok(signature) => {
+                                result.transaction_signature = Some(signature.to_string());
+                            },
+                            Err(e) => println!("Error during hash transfer: {}", e),
+                        }
                     }
-                }
-            },
-            1 => {
-                // Keyword analysis is done in step 0
-            },
-            2 => {
-                for result in analysis_state.results.iter_mut() {
-                    let json_string = serde_json::to_string(&result).map_err(|e| e.to_string())?;
-                    result.compressed_data = zk_compress(&json_string);
-                }
-            },
-            3 => {
-                for result in analysis_state.results.iter_mut() {
-                    match transfer_compressed_hash(
-                        &analysis_state.client,
-                        &analysis_state.account1,
-                        &analysis_state.account2.pubkey(),
-                        &result.compressed_data,
-                    ) {
-                        Ok(signature) => {
-                            result.transaction_signature = Some(signature.to_string());
-                        },
-                        Err(e) => println!("Error during hash transfer: {}", e),
+                },
+                4 => {
+                    let candidates = analysis_state.catalog.read().unwrap_or_else(PoisonError::into_inner).candidates();
+                    if let Some(result) = analysis_state.result.as_mut() {
+                        result.matches = matcher::rank(&result.profile, &candidates);
+                        if let Some(best) = result.matches.first() {
+                            println!("🚨 Matched Blink: {} ({:.2})", best.title, best.score);
+                            best_match = Some(best.clone());
+                        }
                     }
-                }
-            },
-            4 => {
-                save_results_to_file(&analysis_state.results).map_err(|e| e.to_string())?;
-            },
-            5 => {
-                run_python_script().map_err(|e| e.to_string())?;
-            },
-            _ => {}
+                },
+                5 => {
+                    if let Some(result) = &analysis_state.result {
+                        save_results_to_file(result).map_err(|e| e.to_string())?;
+                    }
+                },
+                _ => {}
+            }
+        }
+
+        if let Some(best) = best_match {
+            let actions = state.lock().await.actions.clone();
+            match actions.preview(&best.action_url).await {
+                Ok(preview) => {
+                    window.emit("action_preview", &preview).map_err(|e| e.to_string())?;
+                    if let Some(result) = state.lock().await.result.as_mut() {
+                        result.preview = Some(preview);
+                    }
+                },
+                // The blink page can still render an action we could not
+                Err(e) => {
+                    println!("Could not preview {}: {}", best.action_url, e);
+                    if let Err(e) = open::that(&best.url) {
+                        println!("Could not open {}: {}", best.url, e);
+                    }
+                },
+            }
         }
         
         tokio::time::sleep(Duration::from_secs(1)).await;
     }
     
-    analysis_state.is_running = false;
+    state.lock().await.is_running = false;
     Ok(())
 }
 
@@ -149,49 +238,97 @@ async fn stop_analysis(state: tauri::State<'_, Arc<Mutex<AnalysisState>>>) -> Re
 }
 
 #[tauri::command]
-async fn get_results(state: tauri::State<'_, Arc<Mutex<AnalysisState>>>) -> Result<Vec<AnalysisResult>, String> {
-    let analysis_state = state.lock().await;
-    Ok(analysis_state.results.clone())
+async fn list_history_profiles() -> Result<Vec<history::ProfileInfo>, String> {
+    Ok(history::list_profiles())
 }
 
-fn get_chrome_history_path() -> PathBuf {
-  let home = dirs::home_dir().expect("Unable to find home directory");
-  if cfg!(target_os = "windows") {
-      home.join(r"AppData\Local\Google\Chrome\User Data\Default\History")
-  } else if cfg!(target_os = "macos") {
-      home.join("Library/Application Support/Google/Chrome/Default/History")
-  } else {
-      home.join(".config/google-chrome/Default/History")
-  }
+#[tauri::command]
+async fn preview_action(
+    state: tauri::State<'_, Arc<Mutex<AnalysisState>>>,
+    action_url: String,
+) -> Result<actions::ActionPreview, String> {
+    let actions = state.lock().await.actions.clone();
+    actions.preview(&action_url).await.map_err(|e| e.to_string())
 }
 
-fn extract_links_from_chrome() -> Result<Vec<String>, Box<dyn std::error::Error>> {
-  let history_path = get_chrome_history_path();
-  let temp_path = history_path.with_extension("tmp");
+/// First step of running one button of a previewed action: the action builds
+/// the transaction for our account, which is checked and simulated but not
+/// signed. Nothing is sent until the UI calls `confirm_action` with its id.
+#[tauri::command]
+async fn prepare_action(
+    state: tauri::State<'_, Arc<Mutex<AnalysisState>>>,
+    button: actions::ActionButton,
+    values: Option<HashMap<String, String>>,
+) -> Result<PreparedAction, String> {
+    let href = button.fill(&values.unwrap_or_default()).map_err(|e| e.to_string())?;
+    let (actions, account) = {
+        let analysis_state = state.lock().await;
+        (analysis_state.actions.clone(), analysis_state.account1.pubkey())
+    };
+    let response = actions.post(&href, &account.to_string()).await.map_err(|e| e.to_string())?;
+    let transaction = response
+        .transaction_bytes()
+        .and_then(|bytes| actions::PendingTransaction::decode(&bytes, &account))
+        .map_err(|e| e.to_string())?;
 
-  fs::copy(&history_path, &temp_path)?;
+    let mut analysis_state = state.lock().await;
+    let simulation = simulate_action_transaction(&analysis_state.client, &transaction).map_err(|e| e.to_string())?;
+    let prepared = PreparedAction {
+        id: transaction.message_hash().to_string(),
+        fee_payer: transaction.fee_payer().to_string(),
+        message: response.message.clone(),
+        units_consumed: simulation.units_consumed,
+        logs: simulation.logs.unwrap_or_default(),
+    };
+    analysis_state.pending_action = Some(PendingAction { transaction, message: response.message });
+    Ok(prepared)
+}
 
-  let conn = Connection::open(&temp_path)?;
-  let mut stmt = conn.prepare("SELECT url FROM urls ORDER BY last_visit_time DESC LIMIT 5")?;
-  
-  let urls: Vec<String> = stmt.query_map([], |row| row.get(0))?
-      .filter_map(Result::ok)
-      .collect();
+/// Signs and sends the action `prepare_action` returned as `id`, once the
+/// user has confirmed it.
+#[tauri::command]
+async fn confirm_action(
+    state: tauri::State<'_, Arc<Mutex<AnalysisState>>>,
+    id: String,
+) -> Result<ActionOutcome, String> {
+    let mut analysis_state = state.lock().await;
+    let pending = match analysis_state.pending_action.take() {
+        Some(pending) if pending.transaction.message_hash().to_string() == id => pending,
+        other => {
+            analysis_state.pending_action = other;
+            return Err("This action is not the prepared one; prepare it again".to_string());
+        }
+    };
+    let signature = sign_and_send_action_transaction(&analysis_state.client, &analysis_state.account1, pending.transaction)
+        .map_err(|e| e.to_string())?;
+    Ok(ActionOutcome {
+        signature: signature.to_string(),
+        message: pending.message,
+    })
+}
 
-  fs::remove_file(temp_path)?;
+#[tauri::command]
+async fn cancel_action(state: tauri::State<'_, Arc<Mutex<AnalysisState>>>) -> Result<(), String> {
+    state.lock().await.pending_action = None;
+    Ok(())
+}
 
-  Ok(urls)
+#[tauri::command]
+async fn get_results(state: tauri::State<'_, Arc<Mutex<AnalysisState>>>) -> Result<Option<AnalysisResult>, String> {
+    let analysis_state = state.lock().await;
+    Ok(analysis_state.result.clone())
 }
 
 fn extract_keywords_from_url(url: &str) -> Vec<String> {
   let ignored_words: HashSet<_> = IGNORED_WORDS.iter().map(|&s| s.to_string()).collect();
   
   if let Ok(parsed_url) = Url::parse(url) {
-      let domain = parsed_url.domain().unwrap_or("");
+      let host = domain::split_host(parsed_url.domain().unwrap_or(""));
       let path = parsed_url.path();
       
-      domain.split('.')
-          .chain(path.split('/'))
+      host.subdomains.into_iter()
+          .chain(host.brand)
+          .chain(path.split('/').flat_map(tokenizer::tokenize_segment))
           .filter_map(|segment| {
               let lowercase_segment = segment.to_lowercase();
               if segment.is_empty() || ignored_words.contains(&lowercase_segment) {
@@ -206,22 +343,33 @@ fn extract_keywords_from_url(url: &str) -> Vec<String> {
   }
 }
 
-fn analyze_link(link: &str, word_counter: &mut HashMap<String, u32>) {
-  let keywords = extract_keywords_from_url(link);
+// Counts are keyed by the normalised word; `surface_forms` maps it back to what was seen
+fn analyze_link(link: &str, word_counter: &mut HashMap<String, u32>, surface_forms: &mut normalize::SurfaceForms) {
+  let search_terms = Url::parse(link).map(|url| search::search_terms(&url)).unwrap_or_default();
+  // A results page is about what was searched for, not the engine's host and path
+  let keywords = if search_terms.is_empty() { extract_keywords_from_url(link) } else { Vec::new() };
 
-  for word in keywords {
-      if BLOCKCHAIN_NETWORKS.contains(&word.as_str()) || word.len() > 3 {
-          *word_counter.entry(word).or_insert(0) += 1;
+  let weighted: Vec<(String, u32)> = keywords.into_iter().map(|word| (word, 1))
+      .chain(search_terms.into_iter().map(|term| (term, search::SEARCH_TERM_WEIGHT)))
+      .collect();
+  // Every word of the page is stemmed in the page's language
+  let language = normalize::Language::detect(weighted.iter().map(|(word, _)| word.as_str()));
+  for (word, weight) in weighted {
+      // Taxonomy synonyms are kept even when short ("aave", "zk", "nft")
+      let normalized = if taxonomy::embedded().is_synonym(&word) {
+          Some(normalize::stem(&word))
+      } else if word.chars().count() > 3 {
+          normalize::normalize_as(&word, language)
+      } else {
+          None
+      };
+      if let Some(normalized) = normalized {
+          normalize::record_surface_form(surface_forms, &normalized, &word);
+          *word_counter.entry(normalized).or_insert(0) += weight;
       }
   }
 }
 
-fn get_most_common_word(word_counter: &HashMap<String, u32>) -> Option<(String, u32)> {
-  word_counter.iter()
-      .max_by_key(|&(_, count)| count)
-      .map(|(word, count)| (word.clone(), *count))
-}
-
 fn zk_compress(data: &str) -> String {
   let compressed = general_purpose::STANDARD_NO_PAD.encode(data);
   println!("Compressed data: {}", compressed);
@@ -277,23 +425,20 @@ fn ensure_minimum_balance(client: &RpcClient, pubkey: &Pubkey, minimum_balance:
   
   Err("Failed to ensure minimum balance after multiple attempts".into())
 }
+// Rewards are paid on-chain in Blinks by the solfhe program, so anchoring the
+// hash only needs the memo; no lamports leave the payer besides fees.
 fn transfer_compressed_hash(
   client: &RpcClient,
   payer: &Keypair,
-  to: &Pubkey,
   compressed_hash: &str,
 ) -> Result<Signature, Box<dyn std::error::Error>> {
   ensure_minimum_balance(client, &payer.pubkey(), 1_000_000_000)?; // Ensure 1 SOL minimum
 
-  let rent = client.get_minimum_balance_for_rent_exemption(0)?;
-  let transfer_amount = rent + 1000; // Transfer rent + 1000 lamports
-
-  let transfer_ix = system_instruction::transfer(&payer.pubkey(), to, transfer_amount);
   let memo_ix = spl_memo::build_memo(compressed_hash.as_bytes(), &[&payer.pubkey()]);
   
   let recent_blockhash = client.get_latest_blockhash()?;
   let transaction = Transaction::new_signed_with_payer(
-      &[transfer_ix, memo_ix],
+      &[memo_ix],
       Some(&payer.pubkey()),
       &[payer],
       recent_blockhash,
@@ -306,6 +451,37 @@ fn transfer_compressed_hash(
   Ok(signature)
 }
 
+// Run before our signature is added, so signatures are not verified. The
+// action's blockhash is kept: it is the one that gets sent.
+fn simulate_action_transaction(
+  client: &RpcClient,
+  transaction: &actions::PendingTransaction,
+) -> Result<RpcSimulateTransactionResult, Box<dyn std::error::Error>> {
+  let config = RpcSimulateTransactionConfig {
+      sig_verify: false,
+      ..RpcSimulateTransactionConfig::default()
+  };
+  let simulation = client.simulate_transaction_with_config(transaction.unsigned(), config)?.value;
+  if let Some(err) = simulation.err {
+      let logs = simulation.logs.unwrap_or_default().join("\n");
+      return Err(format!("The action's transaction fails in simulation: {}\n{}", err, logs).into());
+  }
+  Ok(simulation)
+}
+
+// The action has set the blockhash and `PendingTransaction` has checked the
+// fee payer and its signers; we only add our signature
+fn sign_and_send_action_transaction(
+  client: &RpcClient,
+  signer: &Keypair,
+  transaction: actions::PendingTransaction,
+) -> Result<Signature, Box<dyn std::error::Error>> {
+  let transaction = transaction.sign(signer);
+  let signature = client.send_and_confirm_transaction(&transaction)?;
+  println!("⚡ Action transaction confirmed: {}", signature);
+  Ok(signature)
+}
+
 fn retrieve_and_decompress_hash(client: &RpcClient, signature: &Signature) -> Result<String, Box<dyn std::error::Error>> {
   let transaction = client.get_transaction(signature, UiTransactionEncoding::Json)?;
   
@@ -325,54 +501,46 @@ fn retrieve_and_decompress_hash(client: &RpcClient, signature: &Signature) -> Re
   Err("Could not find or process memo in transaction logs".into())
 }
 
-fn save_results_to_file(results: &[AnalysisResult]) -> Result<(), Box<dyn std::error::Error>> {
-  let json_string = serde_json::to_string_pretty(results)?;
+// The profile already covers every run, so it replaces the previous file
+fn save_results_to_file(result: &AnalysisResult) -> Result<(), Box<dyn std::error::Error>> {
+  let json_string = serde_json::to_string_pretty(result)?;
   let mut file = File::create("solfhe.json")?;
   file.write_all(json_string.as_bytes())?;
   println!("Results saved to solfhe.json");
   Ok(())
 }
 
-fn run_python_script() -> Result<(), Box<dyn std::error::Error>> {
-  let current_dir = env::current_dir()?;
-  let script_path = current_dir.join("blink-matcher.py");
-  
-  if !script_path.exists() {
-      return Err("Python script not found".into());
-  }
-
-  let output = Command::new("python3")
-      .arg(script_path)
-      .output()?;
-
-  if output.status.success() {
-      println!("Python script executed successfully");
-      println!("Output: {}", String::from_utf8_lossy(&output.stdout));
-  } else {
-      println!("Python script failed to execute");
-      println!("Error: {}", String::from_utf8_lossy(&output.stderr));
-      return Err("Python script execution failed".into());
-  }
-
-  Ok(())
-}
-
 
 #[tokio::main]
 async fn main() {
   let client = RpcClient::new("http://localhost:8899".to_string());
   let account1 = create_solana_account();
-  let account2 = create_solana_account();
 
   println!("Account 1 public key: {}", account1.pubkey());
-  println!("Account 2 public key: {}", account2.pubkey());
+
+  let catalog_path = catalog::Catalog::default_path();
+  let catalog = catalog::Catalog::load_or_install(&catalog_path).unwrap_or_else(|e| {
+      println!("Could not load the blink catalog {}: {}; using the bundled one", catalog_path.display(), e);
+      catalog::Catalog::bundled()
+  });
+  if catalog.blinks.is_empty() {
+      println!("The blink catalog {} lists no blinks, so no ads will be matched", catalog_path.display());
+  }
+  let catalog = Arc::new(RwLock::new(catalog));
+  // Kept alive until the app exits
+  let _catalog_watcher = catalog::watch(&catalog_path, catalog.clone())
+      .map_err(|e| println!("Blink catalog changes will not be picked up: {}", e))
+      .ok();
 
   let analysis_state = Arc::new(Mutex::new(AnalysisState { 
       is_running: false,
-      results: Vec::new(),
+      result: None,
       client,
       account1,
-      account2,
+      checkpoint_path: Checkpoint::default_path(),
+      catalog,
+      actions: actions::ActionsClient::new(),
+      pending_action: None,
   }));
 
   tauri::Builder::default()
@@ -382,7 +550,16 @@ async fn main() {
           window.set_title("Solfhe Analyzer").unwrap();
           Ok(())
       })
-      .invoke_handler(tauri::generate_handler![start_analysis, stop_analysis, get_results])
+      .invoke_handler(tauri::generate_handler![
+          start_analysis,
+          stop_analysis,
+          get_results,
+          list_history_profiles,
+          preview_action,
+          prepare_action,
+          confirm_action,
+          cancel_action,
+      ])
       .run(tauri::generate_context!())
       .expect("error while running tauri application");
 }
\ No newline at end of file
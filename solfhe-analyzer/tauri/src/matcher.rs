@@ -0,0 +1,311 @@
+// Ranks ad/blink candidates against the interest profile: every candidate and
+// the profile become TF-IDF vectors over one shared vocabulary (sparse, L2
+// normalised) and candidates are scored by cosine similarity to the profile.
+// Candidate text goes through the same tokenizer, stopwords and stemmer as
+// browsing history, so "Staking rewards" meets the profile's "stake".
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::normalize;
+use crate::profile::{self, InterestProfile};
+use crate::tokenizer;
+
+/// Category terms share the vocabulary with keywords under this prefix.
+const CATEGORY_TERM_PREFIX: &str = "category:";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Candidate {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// Taxonomy paths, e.g. `Crypto > DeFi > Lending`.
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// The blink page shown to the user.
+    pub url: String,
+    /// The Solana Action endpoint behind it.
+    pub action_url: String,
+    /// Lamports the advertiser pays per impression; breaks ties between equal scores.
+    #[serde(default)]
+    pub bid: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Match {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    pub action_url: String,
+    /// Cosine similarity to the profile, 0 to 1.
+    pub score: f64,
+}
+
+/// Term index -> weight, sorted by index.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SparseVector(Vec<(usize, f64)>);
+
+impl SparseVector {
+    fn from_weights(weights: HashMap<usize, f64>) -> Self {
+        let mut entries: Vec<(usize, f64)> = weights.into_iter().filter(|&(_, w)| w > 0.0).collect();
+        entries.sort_by_key(|&(index, _)| index);
+        let norm = entries.iter().map(|(_, w)| w * w).sum::<f64>().sqrt();
+        if norm > 0.0 {
+            for (_, weight) in &mut entries {
+                *weight /= norm;
+            }
+        }
+        Self(entries)
+    }
+
+    /// Cosine similarity of two L2 normalised vectors.
+    pub fn dot(&self, other: &Self) -> f64 {
+        let (mut i, mut j, mut sum) = (0, 0, 0.0);
+        while i < self.0.len() && j < other.0.len() {
+            let (a, b) = (self.0[i], other.0[j]);
+            match a.0.cmp(&b.0) {
+                std::cmp::Ordering::Less => i += 1,
+                std::cmp::Ordering::Greater => j += 1,
+                std::cmp::Ordering::Equal => {
+                    sum += a.1 * b.1;
+                    i += 1;
+                    j += 1;
+                },
+            }
+        }
+        sum
+    }
+}
+
+/// A document is a bag of weighted terms.
+pub type Document = HashMap<String, f64>;
+
+#[derive(Debug, Default)]
+pub struct TfidfVectorizer {
+    vocabulary: HashMap<String, usize>,
+    idf: Vec<f64>,
+}
+
+impl TfidfVectorizer {
+    pub fn fit(documents: &[Document]) -> Self {
+        let mut vectorizer = Self::default();
+        let mut document_frequencies: Vec<u32> = Vec::new();
+        for document in documents {
+            // Sorted so term indices do not depend on HashMap order
+            let mut terms: Vec<&String> = document.keys().collect();
+            terms.sort();
+            for term in terms {
+                let next_index = vectorizer.vocabulary.len();
+                let index = *vectorizer.vocabulary.entry(term.clone()).or_insert(next_index);
+                if index == document_frequencies.len() {
+                    document_frequencies.push(0);
+                }
+                document_frequencies[index] += 1;
+            }
+        }
+        vectorizer.idf = document_frequencies
+            .into_iter()
+            .map(|df| profile::idf(documents.len() as u32, df))
+            .collect();
+        vectorizer
+    }
+
+    /// Terms outside the fitted vocabulary are ignored.
+    pub fn transform(&self, document: &Document) -> SparseVector {
+        let weights = document
+            .iter()
+            .filter_map(|(term, &tf)| {
+                let index = *self.vocabulary.get(term)?;
+                Some((index, tf * self.idf[index]))
+            })
+            .collect();
+        SparseVector::from_weights(weights)
+    }
+}
+
+fn category_term(category: &str) -> String {
+    format!("{}{}", CATEGORY_TERM_PREFIX, category.to_lowercase())
+}
+
+/// The profile's keywords and categories, weighted by their interest scores.
+/// Profiles without scores (nothing ingested since scoring existed) fall back
+/// to counts.
+pub fn profile_document(profile: &InterestProfile) -> Document {
+    let mut document = Document::new();
+    for keyword in &profile.top_keywords {
+        let weight = if keyword.score > 0.0 { keyword.score } else { keyword.count as f64 };
+        *document.entry(keyword.keyword.clone()).or_insert(0.0) += weight;
+    }
+    for category in &profile.categories {
+        let weight = if category.score > 0.0 { category.score } else { category.count as f64 };
+        *document.entry(category_term(&category.category)).or_insert(0.0) += weight;
+    }
+    document
+}
+
+pub fn candidate_document(candidate: &Candidate) -> Document {
+    let mut document = Document::new();
+    let text = [candidate.title.as_str(), candidate.description.as_str()];
+    let words: Vec<String> = text
+        .iter()
+        .flat_map(|text| text.split_whitespace())
+        .chain(candidate.keywords.iter().map(String::as_str))
+        .flat_map(tokenizer::tokenize_segment)
+        .collect();
+    let language = normalize::Language::detect(words.iter().map(String::as_str));
+    for word in &words {
+        if let Some(term) = normalize::normalize_as(word, language) {
+            *document.entry(term).or_insert(0.0) += 1.0;
+        }
+    }
+    for category in &candidate.categories {
+        // A category also matches profile interest in its parent categories
+        let mut path = String::new();
+        for part in category.split(crate::taxonomy::PATH_SEPARATOR) {
+            if !path.is_empty() {
+                path.push_str(crate::taxonomy::PATH_SEPARATOR);
+            }
+            path.push_str(part.trim());
+            *document.entry(category_term(&path)).or_insert(0.0) += 1.0;
+        }
+    }
+    document
+}
+
+/// Candidates similar to the profile, best first; ties go to the higher bid,
+/// then are ordered by id.
+pub fn rank(profile: &InterestProfile, candidates: &[Candidate]) -> Vec<Match> {
+    let mut documents: Vec<Document> = candidates.iter().map(candidate_document).collect();
+    documents.push(profile_document(profile));
+    let vectorizer = TfidfVectorizer::fit(&documents);
+
+    let profile_vector = vectorizer.transform(&documents[candidates.len()]);
+    let mut matches: Vec<(Match, u64)> = candidates
+        .iter()
+        .zip(&documents)
+        .map(|(candidate, document)| {
+            let candidate_match = Match {
+                id: candidate.id.clone(),
+                title: candidate.title.clone(),
+                url: candidate.url.clone(),
+                action_url: candidate.action_url.clone(),
+                score: vectorizer.transform(document).dot(&profile_vector),
+            };
+            (candidate_match, candidate.bid)
+        })
+        .filter(|(m, _)| m.score > 0.0)
+        .collect();
+    matches.sort_by(|(a, a_bid), (b, b_bid)| {
+        b.score
+            .total_cmp(&a.score)
+            .then(b_bid.cmp(a_bid))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    matches.into_iter().map(|(m, _)| m).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profile::{CategoryInterest, KeywordScore};
+
+    fn doc(terms: &[(&str, f64)]) -> Document {
+        terms.iter().map(|&(term, weight)| (term.to_string(), weight)).collect()
+    }
+
+    fn keyword(keyword: &str, score: f64) -> KeywordScore {
+        KeywordScore {
+            keyword: keyword.to_string(),
+            count: 1,
+            score,
+            tf_idf: 0.0,
+            surface_forms: Vec::new(),
+        }
+    }
+
+    fn candidate(id: &str, description: &str, categories: &[&str]) -> Candidate {
+        Candidate {
+            id: id.to_string(),
+            title: id.to_string(),
+            description: description.to_string(),
+            keywords: Vec::new(),
+            categories: categories.iter().map(|c| c.to_string()).collect(),
+            url: format!("https://example.com/{}", id),
+            action_url: format!("https://example.com/api/{}", id),
+            bid: 0,
+        }
+    }
+
+    #[test]
+    fn test_tfidf_cosine_matches_scikit_learn() {
+        // TfidfVectorizer().fit_transform(["solana stake stake", "solana swap"]);
+        // cosine_similarity(...) == 0.19431...
+        let documents = vec![doc(&[("solana", 1.0), ("stake", 2.0)]), doc(&[("solana", 1.0), ("swap", 1.0)])];
+        let vectorizer = TfidfVectorizer::fit(&documents);
+        let a = vectorizer.transform(&documents[0]);
+        let b = vectorizer.transform(&documents[1]);
+        assert!((a.dot(&a) - 1.0).abs() < 1e-12);
+        assert!((a.dot(&b) - 0.194_314).abs() < 1e-6);
+        assert_eq!(vectorizer.transform(&doc(&[("unknown", 1.0)])), SparseVector::default());
+    }
+
+    #[test]
+    fn test_rank() {
+        let profile = InterestProfile {
+            top_keywords: vec![keyword("stake", 3.0), keyword("solana", 1.0)],
+            categories: vec![CategoryInterest { category: "Crypto > DeFi > Staking".to_string(), count: 3, score: 2.0 }],
+            ..InterestProfile::default()
+        };
+        let candidates = vec![
+            candidate("lending", "Borrow against your assets", &["Crypto > DeFi > Lending"]),
+            candidate("liquid-staking", "Staking rewards on Solana validators", &["Crypto > DeFi > Staking"]),
+            candidate("cooking", "Recipes for dinner", &[]),
+        ];
+        let matches = rank(&profile, &candidates);
+        let ids: Vec<&str> = matches.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["liquid-staking"]);
+        assert!(matches[0].score > 0.5 && matches[0].score <= 1.0);
+
+        // The parent category alone is still a (weaker) match
+        let profile = InterestProfile {
+            categories: vec![CategoryInterest { category: "Crypto > DeFi".to_string(), count: 1, score: 1.0 }],
+            ..InterestProfile::default()
+        };
+        let ids: Vec<String> = rank(&profile, &candidates).into_iter().map(|m| m.id).collect();
+        assert_eq!(ids, vec!["lending", "liquid-staking"]);
+    }
+
+    #[test]
+    fn test_rank_ties_go_to_higher_bid() {
+        let profile = InterestProfile {
+            top_keywords: vec![keyword("stake", 1.0)],
+            ..InterestProfile::default()
+        };
+        // Same text, so the same score
+        let mut candidates: Vec<Candidate> = ["a", "b", "c"]
+            .iter()
+            .map(|id| Candidate { title: "Stake".to_string(), ..candidate(id, "Stake", &[]) })
+            .collect();
+        candidates[1].bid = 500;
+        candidates[2].bid = 100;
+        let matches = rank(&profile, &candidates);
+        assert!(matches.iter().all(|m| m.score == matches[0].score));
+        let ids: Vec<&str> = matches.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "c", "a"]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_bundled_catalog_candidates() {
+        let profile = InterestProfile {
+            top_keywords: vec![keyword("superteam", 2.0), keyword("bounti", 1.0)],
+            ..InterestProfile::default()
+        };
+        let matches = rank(&profile, &crate::catalog::Catalog::bundled().candidates());
+        assert_eq!(matches[0].id, "superteam-earn");
+        assert_eq!(matches[0].action_url, "http://localhost:3001/api/action");
+    }
+}
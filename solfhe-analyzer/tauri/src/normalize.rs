@@ -0,0 +1,163 @@
+// Keyword normalisation: English and Turkish stopwords (data/stopwords/) are
+// dropped and the remaining words are stemmed, so "tokens" and "token" count as
+// one keyword. A page's words are all stemmed in the language whose stopwords
+// it uses most. Counts are keyed by the stem; `SurfaceForms` keeps the words
+// that were actually seen for each stem.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::OnceLock;
+
+use rust_stemmers::{Algorithm, Stemmer};
+
+static ENGLISH_STOPWORDS: &str = include_str!("../data/stopwords/en.txt");
+static TURKISH_STOPWORDS: &str = include_str!("../data/stopwords/tr.txt");
+
+// Letters only Turkish uses; they settle the language when the stopwords do not
+const TURKISH_LETTERS: [char; 6] = ['ç', 'ğ', 'ı', 'ö', 'ş', 'ü'];
+
+/// Stem -> the surface forms it was built from.
+pub type SurfaceForms = HashMap<String, BTreeSet<String>>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Language {
+    English,
+    Turkish,
+}
+
+impl Language {
+    /// The language whose stopwords the words use most. Ties, including pages
+    /// with no stopwords at all, go to Turkish if any word has Turkish-only
+    /// letters and to English otherwise.
+    pub fn detect<'a>(words: impl IntoIterator<Item = &'a str>) -> Self {
+        let (mut english, mut turkish, mut turkish_letters) = (0, 0, false);
+        for word in words {
+            let word = word.to_lowercase();
+            english += usize::from(english_stopwords().contains(word.as_str()));
+            turkish += usize::from(turkish_stopwords().contains(word.as_str()));
+            turkish_letters |= word.contains(TURKISH_LETTERS);
+        }
+        if turkish > english || (turkish == english && turkish_letters) {
+            Language::Turkish
+        } else {
+            Language::English
+        }
+    }
+
+    fn stemmer(self) -> &'static Stemmer {
+        static ENGLISH: OnceLock<Stemmer> = OnceLock::new();
+        static TURKISH: OnceLock<Stemmer> = OnceLock::new();
+        match self {
+            Language::English => ENGLISH.get_or_init(|| Stemmer::create(Algorithm::English)),
+            Language::Turkish => TURKISH.get_or_init(|| Stemmer::create(Algorithm::Turkish)),
+        }
+    }
+}
+
+fn parse_stopwords(list: &'static str) -> HashSet<&'static str> {
+    list.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect()
+}
+
+fn english_stopwords() -> &'static HashSet<&'static str> {
+    static STOPWORDS: OnceLock<HashSet<&'static str>> = OnceLock::new();
+    STOPWORDS.get_or_init(|| parse_stopwords(ENGLISH_STOPWORDS))
+}
+
+fn turkish_stopwords() -> &'static HashSet<&'static str> {
+    static STOPWORDS: OnceLock<HashSet<&'static str>> = OnceLock::new();
+    STOPWORDS.get_or_init(|| parse_stopwords(TURKISH_STOPWORDS))
+}
+
+pub fn is_stopword(word: &str) -> bool {
+    english_stopwords().contains(word) || turkish_stopwords().contains(word)
+}
+
+/// Stems a lowercase word on its own, in the language `Language::detect`
+/// picks for just that word.
+pub fn stem(word: &str) -> String {
+    stem_as(word, Language::detect([word]))
+}
+
+/// Stems a lowercase word with the stemmer for `language`.
+pub fn stem_as(word: &str, language: Language) -> String {
+    language.stemmer().stem(word).into_owned()
+}
+
+/// The normalised form of a word, or `None` for stopwords.
+pub fn normalize(word: &str) -> Option<String> {
+    normalize_as(word, Language::detect([word]))
+}
+
+/// `normalize` for a word of a page whose language is already known.
+pub fn normalize_as(word: &str, language: Language) -> Option<String> {
+    let word = word.to_lowercase();
+    if is_stopword(&word) {
+        None
+    } else {
+        Some(stem_as(&word, language))
+    }
+}
+
+pub fn record_surface_form(forms: &mut SurfaceForms, stem: &str, surface: &str) {
+    forms.entry(stem.to_string()).or_default().insert(surface.to_string());
+}
+
+pub fn merge_surface_forms(into: &mut SurfaceForms, from: &SurfaceForms) {
+    for (stem, surfaces) in from {
+        into.entry(stem.clone()).or_default().extend(surfaces.iter().cloned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("tokens"), normalize("token"));
+        assert_eq!(normalize("Staking").as_deref(), Some("stake"));
+        assert_eq!(normalize("with"), None);
+        assert_eq!(normalize("about"), None);
+        assert_eq!(normalize("için"), None);
+        assert_eq!(normalize("nasıl"), None);
+        // Turkish suffixes are removed when the word has Turkish letters
+        assert_eq!(normalize("cüzdanları").as_deref(), Some("cüzdan"));
+    }
+
+    #[test]
+    fn test_language_per_page() {
+        // Turkish stopwords pick the Turkish stemmer for every word of the page,
+        // including words without Turkish-only letters
+        let turkish = ["bitcoin", "nasıl", "alınır", "ve", "kitaplar", "için"];
+        assert_eq!(Language::detect(turkish), Language::Turkish);
+        assert_eq!(normalize_as("kitaplar", Language::Turkish).as_deref(), Some("kitap"));
+        // On its own the word has no Turkish letters and is left to the English stemmer
+        assert_eq!(normalize("kitaplar").as_deref(), Some("kitaplar"));
+
+        let english = ["how", "to", "stake", "with", "the", "öküz", "wallet"];
+        assert_eq!(Language::detect(english), Language::English);
+        assert_eq!(normalize_as("wallets", Language::English).as_deref(), Some("wallet"));
+
+        // Without stopwords the letters decide
+        assert_eq!(Language::detect(["solana", "staking"]), Language::English);
+        assert_eq!(Language::detect(["solana", "cüzdan"]), Language::Turkish);
+    }
+
+    #[test]
+    fn test_surface_forms() {
+        let mut forms = SurfaceForms::new();
+        record_surface_form(&mut forms, "token", "tokens");
+        record_surface_form(&mut forms, "token", "token");
+        record_surface_form(&mut forms, "token", "tokens");
+
+        let mut merged = SurfaceForms::new();
+        record_surface_form(&mut merged, "token", "tokenized");
+        merge_surface_forms(&mut merged, &forms);
+        assert_eq!(
+            merged["token"].iter().collect::<Vec<_>>(),
+            vec!["token", "tokenized", "tokens"]
+        );
+    }
+}
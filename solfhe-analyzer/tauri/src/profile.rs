@@ -0,0 +1,164 @@
+// The user's interest profile, aggregated over every visit ingested into the
+// checkpoint. It is the one artifact that is compressed and anchored on-chain.
+
+use serde::{Deserialize, Serialize};
+
+use crate::checkpoint::Checkpoint;
+use crate::scoring::ScoringConfig;
+
+pub const TOP_KEYWORDS: usize = 20;
+/// Scores are rounded so the profile (and its on-chain hash) does not depend
+/// on the last bits of floating point arithmetic.
+const SCORE_PRECISION: f64 = 1e4;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct KeywordScore {
+    /// The normalised (stemmed) keyword.
+    pub keyword: String,
+    /// Occurrences across all visits.
+    pub count: u32,
+    /// Recency- and frequency-weighted interest, see `scoring`.
+    pub score: f64,
+    /// `count` weighted by the keyword's inverse document frequency across
+    /// the pages in the history.
+    pub tf_idf: f64,
+    pub surface_forms: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CategoryInterest {
+    pub category: String,
+    /// Taxonomy matches across all visits.
+    pub count: u32,
+    pub score: f64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct InterestProfile {
+    /// Unix seconds.
+    pub generated_at: i64,
+    pub total_links_analyzed: u32,
+    pub total_unique_words: u32,
+    /// Highest score first, ties by count and then keyword.
+    pub top_keywords: Vec<KeywordScore>,
+    /// Highest score first, ties by count and then category path.
+    pub categories: Vec<CategoryInterest>,
+}
+
+/// Smoothed IDF, as in scikit-learn: `ln((1 + n) / (1 + df)) + 1`.
+pub fn idf(documents: u32, document_frequency: u32) -> f64 {
+    ((1.0 + documents as f64) / (1.0 + document_frequency as f64)).ln() + 1.0
+}
+
+fn round(value: f64) -> f64 {
+    (value * SCORE_PRECISION).round() / SCORE_PRECISION
+}
+
+impl InterestProfile {
+    pub fn from_checkpoint(checkpoint: &Checkpoint, top_n: usize, now: i64, config: &ScoringConfig) -> Self {
+        let scores = checkpoint.scores.at(now, config);
+        let mut keywords: Vec<KeywordScore> = checkpoint
+            .keyword_counts
+            .iter()
+            .map(|(keyword, &count)| {
+                let df = checkpoint.document_frequencies.get(keyword).copied().unwrap_or(0);
+                KeywordScore {
+                    keyword: keyword.clone(),
+                    count,
+                    score: round(scores.keywords.get(keyword).copied().unwrap_or(0.0)),
+                    tf_idf: round(count as f64 * idf(checkpoint.documents, df)),
+                    surface_forms: checkpoint
+                        .surface_forms
+                        .get(keyword)
+                        .map(|forms| forms.iter().cloned().collect())
+                        .unwrap_or_default(),
+                }
+            })
+            .collect();
+        keywords.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then(b.count.cmp(&a.count))
+                .then_with(|| a.keyword.cmp(&b.keyword))
+        });
+        keywords.truncate(top_n);
+
+        let mut categories: Vec<CategoryInterest> = checkpoint
+            .category_scores
+            .iter()
+            .map(|(category, &count)| CategoryInterest {
+                category: category.clone(),
+                count,
+                score: round(scores.categories.get(category).copied().unwrap_or(0.0)),
+            })
+            .collect();
+        categories.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then(b.count.cmp(&a.count))
+                .then_with(|| a.category.cmp(&b.category))
+        });
+
+        Self {
+            generated_at: now,
+            total_links_analyzed: checkpoint.documents,
+            total_unique_words: checkpoint.keyword_counts.len() as u32,
+            top_keywords: keywords,
+            categories,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    use crate::taxonomy::CategoryScore;
+
+    fn page(words: &[(&str, u32)]) -> HashMap<String, u32> {
+        words.iter().map(|&(word, count)| (word.to_string(), count)).collect()
+    }
+
+    #[test]
+    fn test_profile_from_checkpoint() {
+        let mut checkpoint = Checkpoint::default();
+        checkpoint.merge_keywords("https://solana.com/staking", &page(&[("solana", 1), ("stake", 1)]), 2);
+        checkpoint.merge_keywords("https://gemini.com/solana", &page(&[("solana", 1), ("gemini", 2)]), 1);
+        checkpoint.merge_keywords("https://solana.com/", &page(&[("solana", 1)]), 1);
+        checkpoint.merge_categories(
+            &[
+                CategoryScore { category: "Crypto".to_string(), score: 2 },
+                CategoryScore { category: "Technology".to_string(), score: 1 },
+            ],
+            2,
+        );
+        crate::normalize::record_surface_form(&mut checkpoint.surface_forms, "stake", "staking");
+
+        // Without scores the ranking falls back to counts
+        let config = ScoringConfig::default();
+        let profile = InterestProfile::from_checkpoint(&checkpoint, 3, 1_700_000_000, &config);
+        assert_eq!(profile.generated_at, 1_700_000_000);
+        assert_eq!(profile.total_links_analyzed, 3);
+        assert_eq!(profile.total_unique_words, 3);
+
+        let ranked: Vec<(&str, u32)> = profile.top_keywords.iter().map(|k| (k.keyword.as_str(), k.count)).collect();
+        // "stake" and "gemini" tie on count, and both are on one page, so TF-IDF ties too
+        assert_eq!(ranked, vec![("solana", 4), ("gemini", 2), ("stake", 2)]);
+        assert_eq!(profile.top_keywords[2].surface_forms, vec!["staking"]);
+        // A keyword on every page is weighted less than one on a single page
+        assert_eq!(profile.top_keywords[0].tf_idf, 4.0);
+        assert!((profile.top_keywords[1].tf_idf - 2.0 * (1.0 + 2f64.ln())).abs() < 1e-4);
+
+        assert_eq!(profile.categories[0].category, "Crypto");
+        assert_eq!(profile.categories[0].count, 4);
+        assert_eq!(InterestProfile::from_checkpoint(&checkpoint, 1, 0, &config).top_keywords.len(), 1);
+
+        // A higher score outranks a higher count
+        checkpoint.scores.scored_at = 1_700_000_000;
+        checkpoint.scores.add_page(&page(&[("gemini", 1)]), &[], 3.0);
+        let profile = InterestProfile::from_checkpoint(&checkpoint, 3, 1_700_000_000, &config);
+        assert_eq!(profile.top_keywords[0].keyword, "gemini");
+        assert_eq!(profile.top_keywords[0].score, 3.0);
+    }
+}
@@ -0,0 +1,235 @@
+// Recency- and frequency-weighted interest scores. A page's keywords count
+// with a weight that halves every `half_life_days` since the visit, grows for
+// URLs the user typed, and grows sublinearly with repeated visits so a page
+// that was reloaded fifty times does not drown out everything else. Scores are
+// kept decayed to `scored_at`, so each run only has to age the stored totals
+// before adding the new visits.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::history::HistoryEntry;
+use crate::taxonomy::CategoryScore;
+
+const SECS_PER_DAY: f64 = 86_400.0;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoringConfig {
+    /// Days after which a visit counts half as much; 0 disables decay.
+    pub half_life_days: f64,
+    /// Multiplier for pages the user typed into the address bar.
+    pub typed_boost: f64,
+    /// `n` visits count as `n ^ reload_exponent`; 1.0 counts every visit fully.
+    pub reload_exponent: f64,
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        Self {
+            half_life_days: 14.0,
+            typed_boost: 1.5,
+            reload_exponent: 0.5,
+        }
+    }
+}
+
+impl ScoringConfig {
+    pub fn decay(&self, elapsed_secs: i64) -> f64 {
+        if self.half_life_days <= 0.0 {
+            return 1.0;
+        }
+        0.5f64.powf(elapsed_secs.max(0) as f64 / (self.half_life_days * SECS_PER_DAY))
+    }
+
+    /// The weight of a page's new visits at `now`. All new visits are aged
+    /// from the page's last visit, the only timestamp the history query keeps.
+    pub fn visit_weight(&self, entry: &HistoryEntry, now: i64) -> f64 {
+        let visits = (entry.new_visits.max(1) as f64).powf(self.reload_exponent);
+        let typed = if entry.typed_count > 0 { self.typed_boost } else { 1.0 };
+        visits * typed * self.decay(now - entry.last_visit)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct InterestScores {
+    /// Unix seconds the scores are decayed to.
+    pub scored_at: i64,
+    /// Keyed by normalised keyword.
+    pub keywords: HashMap<String, f64>,
+    /// Keyed by category path.
+    pub categories: HashMap<String, f64>,
+}
+
+impl InterestScores {
+    /// Ages every score from `scored_at` to `now`.
+    pub fn decay_to(&mut self, now: i64, config: &ScoringConfig) {
+        if now <= self.scored_at {
+            return;
+        }
+        let factor = config.decay(now - self.scored_at);
+        for score in self.keywords.values_mut().chain(self.categories.values_mut()) {
+            *score *= factor;
+        }
+        self.scored_at = now;
+    }
+
+    /// Adds one page; `weight` comes from `ScoringConfig::visit_weight` at `scored_at`.
+    pub fn add_page(&mut self, counts: &HashMap<String, u32>, categories: &[CategoryScore], weight: f64) {
+        for (word, &count) in counts {
+            *self.keywords.entry(word.clone()).or_insert(0.0) += count as f64 * weight;
+        }
+        for category in categories {
+            *self.categories.entry(category.category.clone()).or_insert(0.0) += category.score as f64 * weight;
+        }
+    }
+
+    /// A copy aged to `now`, for reporting without changing the stored scores.
+    pub fn at(&self, now: i64, config: &ScoringConfig) -> Self {
+        let mut scores = self.clone();
+        scores.decay_to(now, config);
+        scores
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkpoint::Checkpoint;
+    use crate::normalize::SurfaceForms;
+    use crate::profile::InterestProfile;
+
+    const DAY: i64 = 86_400;
+
+    /// Highest score first; equal scores are ordered by key so results never
+    /// depend on `HashMap` iteration order.
+    fn ranked(scores: &HashMap<String, f64>) -> Vec<(&str, f64)> {
+        let mut ranked: Vec<(&str, f64)> = scores.iter().map(|(key, &score)| (key.as_str(), score)).collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        ranked
+    }
+
+    #[derive(Deserialize)]
+    struct Fixture {
+        now: i64,
+        #[serde(default)]
+        config: ScoringConfig,
+        /// Each run ingests its entries at its own time, like `start_analysis`.
+        runs: Vec<FixtureRun>,
+    }
+
+    #[derive(Deserialize)]
+    struct FixtureRun {
+        at: i64,
+        entries: Vec<FixtureEntry>,
+    }
+
+    #[derive(Deserialize)]
+    struct FixtureEntry {
+        url: String,
+        #[serde(default)]
+        typed_count: u32,
+        last_visit: i64,
+        new_visits: u32,
+        /// Normalised keyword counts as `analyze_link` would produce them.
+        keywords: HashMap<String, u32>,
+    }
+
+    fn profile_for(fixture: &str) -> InterestProfile {
+        let fixture: Fixture = serde_json::from_str(fixture).unwrap();
+        let mut checkpoint = Checkpoint::default();
+        for run in fixture.runs {
+            let pages = run.entries.into_iter().map(|fixture_entry| {
+                let entry = HistoryEntry {
+                    url: fixture_entry.url,
+                    browser: "Chrome".to_string(),
+                    profile: "Default".to_string(),
+                    visit_count: fixture_entry.new_visits,
+                    typed_count: fixture_entry.typed_count,
+                    last_visit: fixture_entry.last_visit,
+                    new_visits: fixture_entry.new_visits,
+                    last_visit_id: 0,
+                };
+                (entry, fixture_entry.keywords, SurfaceForms::new())
+            });
+            checkpoint.ingest(pages, run.at, &fixture.config);
+        }
+        InterestProfile::from_checkpoint(&checkpoint, 10, fixture.now, &fixture.config)
+    }
+
+    #[test]
+    fn test_decay_and_visit_weight() {
+        let config = ScoringConfig::default();
+        assert_eq!(config.decay(0), 1.0);
+        assert!((config.decay(14 * DAY) - 0.5).abs() < 1e-12);
+        assert!((config.decay(28 * DAY) - 0.25).abs() < 1e-12);
+        // Visits in the future (clock skew) are not boosted
+        assert_eq!(config.decay(-DAY), 1.0);
+        assert_eq!(ScoringConfig { half_life_days: 0.0, ..config.clone() }.decay(1_000 * DAY), 1.0);
+
+        let entry = |typed_count, new_visits| HistoryEntry {
+            url: "https://solana.com/".to_string(),
+            browser: "Chrome".to_string(),
+            profile: "Default".to_string(),
+            visit_count: new_visits,
+            typed_count,
+            last_visit: 100 * DAY,
+            new_visits,
+            last_visit_id: 1,
+        };
+        assert_eq!(config.visit_weight(&entry(0, 1), 100 * DAY), 1.0);
+        assert_eq!(config.visit_weight(&entry(1, 1), 100 * DAY), 1.5);
+        assert_eq!(config.visit_weight(&entry(0, 16), 100 * DAY), 4.0);
+        assert!((config.visit_weight(&entry(0, 16), 114 * DAY) - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_decay_to_is_incremental() {
+        let config = ScoringConfig::default();
+        let counts = HashMap::from([("solana".to_string(), 2)]);
+
+        // Aging in two steps equals aging once
+        let mut stepwise = InterestScores { scored_at: 0, ..InterestScores::default() };
+        stepwise.add_page(&counts, &[], 1.0);
+        stepwise.decay_to(7 * DAY, &config);
+        stepwise.decay_to(14 * DAY, &config);
+        stepwise.decay_to(10 * DAY, &config);
+        let at_once = {
+            let mut scores = InterestScores::default();
+            scores.add_page(&counts, &[], 1.0);
+            scores.at(14 * DAY, &config)
+        };
+        assert!((stepwise.keywords["solana"] - 1.0).abs() < 1e-12);
+        assert!((at_once.keywords["solana"] - 1.0).abs() < 1e-12);
+        assert_eq!(stepwise.scored_at, 14 * DAY);
+    }
+
+    #[test]
+    fn test_ranked_is_deterministic() {
+        let scores: HashMap<String, f64> = ["swap", "aave", "stake", "nft"]
+            .iter()
+            .map(|word| (word.to_string(), 1.0))
+            .chain([("solana".to_string(), 2.0)])
+            .collect();
+        assert_eq!(
+            ranked(&scores),
+            vec![("solana", 2.0), ("aave", 1.0), ("nft", 1.0), ("stake", 1.0), ("swap", 1.0)]
+        );
+    }
+
+    #[test]
+    fn test_snapshot_recent_beats_stale() {
+        insta::assert_json_snapshot!(profile_for(include_str!("../tests/fixtures/scoring/recent_beats_stale.json")));
+    }
+
+    #[test]
+    fn test_snapshot_typed_and_reloaded() {
+        insta::assert_json_snapshot!(profile_for(include_str!("../tests/fixtures/scoring/typed_and_reloaded.json")));
+    }
+
+    #[test]
+    fn test_snapshot_incremental_runs() {
+        insta::assert_json_snapshot!(profile_for(include_str!("../tests/fixtures/scoring/incremental_runs.json")));
+    }
+}
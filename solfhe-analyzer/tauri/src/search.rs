@@ -0,0 +1,83 @@
+// Search terms from search engine result URLs. What the user typed is a
+// stronger interest signal than path segments, so `analyze_link` counts each
+// term `SEARCH_TERM_WEIGHT` times.
+
+use url::Url;
+
+use crate::domain;
+
+pub const SEARCH_TERM_WEIGHT: u32 = 3;
+
+// (brand, query parameters holding the search text)
+const SEARCH_ENGINES: [(&str, &[&str]); 7] = [
+    ("google", &["q", "query"]),
+    ("bing", &["q"]),
+    ("duckduckgo", &["q"]),
+    ("brave", &["q"]),
+    ("youtube", &["search_query", "q"]),
+    ("yahoo", &["p"]),
+    ("baidu", &["wd"]),
+];
+
+/// The search parameters for the URL's engine, if the URL is a search engine's.
+fn engine_params(url: &Url) -> Option<&'static [&'static str]> {
+    let brand = domain::split_host(url.domain()?).brand?;
+    SEARCH_ENGINES
+        .iter()
+        .find(|(engine, _)| *engine == brand)
+        .map(|(_, params)| *params)
+}
+
+/// Percent-decoded, lowercased words of the search text, or nothing for
+/// URLs that are not search engine queries.
+pub fn search_terms(url: &Url) -> Vec<String> {
+    let Some(params) = engine_params(url) else {
+        return Vec::new();
+    };
+    // `query_pairs` percent-decodes and turns `+` into spaces
+    let Some(text) = params
+        .iter()
+        .find_map(|param| url.query_pairs().find(|(key, _)| key == param).map(|(_, value)| value))
+    else {
+        return Vec::new();
+    };
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(url: &str) -> Vec<String> {
+        search_terms(&Url::parse(url).unwrap())
+    }
+
+    #[test]
+    fn test_search_terms() {
+        assert_eq!(
+            terms("https://www.google.com/search?q=solana+zk+compression&oq=solana"),
+            vec!["solana", "zk", "compression"]
+        );
+        assert_eq!(terms("https://www.google.co.uk/search?q=Light%20Protocol"), vec!["light", "protocol"]);
+        assert_eq!(terms("https://www.bing.com/search?form=QBLH&q=phantom%2Bwallet"), vec!["phantom", "wallet"]);
+        assert_eq!(terms("https://duckduckgo.com/?q=%C3%A7ekirdek+node&ia=web"), vec!["çekirdek", "node"]);
+        assert_eq!(terms("https://search.brave.com/search?q=jupiter-swap"), vec!["jupiter", "swap"]);
+        assert_eq!(
+            terms("https://www.youtube.com/results?search_query=solana+breakpoint"),
+            vec!["solana", "breakpoint"]
+        );
+        assert_eq!(terms("https://www.baidu.com/s?wd=solana"), vec!["solana"]);
+        assert_eq!(terms("https://search.yahoo.com/search?p=helius+rpc"), vec!["helius", "rpc"]);
+    }
+
+    #[test]
+    fn test_non_search_urls() {
+        // Only search engines' query parameters are search terms
+        assert!(terms("https://example.com/?q=solana").is_empty());
+        assert!(terms("https://www.google.com/maps").is_empty());
+        assert!(terms("https://www.google.com/search?tbm=isch").is_empty());
+    }
+}
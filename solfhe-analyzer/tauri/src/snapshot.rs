@@ -0,0 +1,166 @@
+// Read-only snapshots of browser databases. The browser keeps its database
+// open (and on Windows locked), so the file is copied together with its `-wal`
+// and `-journal` files into an app-owned temporary directory and queried
+// there. SQLite replays committed WAL frames on open and ignores uncommitted
+// ones, so the copy is consistent. The directory is removed when the snapshot
+// is dropped, even if reading it fails.
+
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use rusqlite::Connection;
+use tempfile::TempDir;
+
+const SIDE_FILE_SUFFIXES: [&str; 2] = ["-wal", "-journal"];
+const COPY_ATTEMPTS: usize = 3;
+
+pub struct Snapshot {
+    db_path: PathBuf,
+    // Dropped last; deletes the copied files
+    _dir: TempDir,
+}
+
+impl Snapshot {
+    pub fn create(source: &Path) -> Result<Self, Box<dyn Error>> {
+        let file_name = source.file_name().ok_or("database path has no file name")?;
+        let dir = tempfile::Builder::new().prefix("solfhe-snapshot-").tempdir()?;
+        let db_path = dir.path().join(file_name);
+
+        // Retry when the browser wrote to the database or its side files while
+        // they were being copied; most writes only touch the WAL
+        for _ in 0..COPY_ATTEMPTS {
+            let before = modified(source)?;
+            copy_with_side_files(source, &db_path)?;
+            if modified(source)? == before {
+                return Ok(Self { db_path, _dir: dir });
+            }
+        }
+        println!("{} kept changing while copying; using the last copy", source.display());
+        Ok(Self { db_path, _dir: dir })
+    }
+
+    #[cfg(test)]
+    pub fn path(&self) -> &Path {
+        &self.db_path
+    }
+
+    /// Opens the copy; the connection must be dropped before the snapshot.
+    pub fn open(&self) -> rusqlite::Result<Connection> {
+        Connection::open(&self.db_path)
+    }
+}
+
+/// Modification time and length of the database and each side file. A
+/// missing side file is `None`, so one appearing or going away is a change too.
+fn modified(path: &Path) -> std::io::Result<Vec<Option<(SystemTime, u64)>>> {
+    let mut state = vec![Some(file_state(path)?)];
+    for suffix in SIDE_FILE_SUFFIXES {
+        match file_state(&with_suffix(path, suffix)) {
+            Ok(side_state) => state.push(Some(side_state)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => state.push(None),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(state)
+}
+
+fn file_state(path: &Path) -> std::io::Result<(SystemTime, u64)> {
+    let metadata = fs::metadata(path)?;
+    Ok((metadata.modified()?, metadata.len()))
+}
+
+fn copy_with_side_files(source: &Path, target: &Path) -> std::io::Result<()> {
+    fs::copy(source, target)?;
+    for suffix in SIDE_FILE_SUFFIXES {
+        let side_target = with_suffix(target, suffix);
+        match fs::copy(with_suffix(source, suffix), &side_target) {
+            Ok(_) => {}
+            // No side file means nothing is pending; drop one left by an earlier attempt
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let _ = fs::remove_file(&side_target);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(conn: &Connection) -> i64 {
+        conn.query_row("SELECT COUNT(*) FROM urls", [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn test_snapshot_mid_wal_transaction() {
+        let source_dir = tempfile::tempdir().unwrap();
+        let source = source_dir.path().join("History");
+
+        // The browser's connection: committed rows live only in the WAL, and a
+        // write transaction is still open when the snapshot is taken
+        let browser = Connection::open(&source).unwrap();
+        browser
+            .execute_batch(
+                "PRAGMA journal_mode = WAL;
+                 PRAGMA wal_autocheckpoint = 0;
+                 CREATE TABLE urls (id INTEGER PRIMARY KEY, url TEXT);
+                 INSERT INTO urls (url) VALUES ('https://solana.com/'), ('https://example.com/');
+                 BEGIN IMMEDIATE;
+                 INSERT INTO urls (url) VALUES ('https://uncommitted.example/');",
+            )
+            .unwrap();
+        assert!(with_suffix(&source, "-wal").exists());
+
+        let snapshot = Snapshot::create(&source).unwrap();
+        let snapshot_dir = snapshot.path().parent().unwrap().to_path_buf();
+        assert!(snapshot_dir.starts_with(std::env::temp_dir()));
+        assert!(with_suffix(snapshot.path(), "-wal").exists());
+        {
+            let conn = snapshot.open().unwrap();
+            assert_eq!(count(&conn), 2);
+        }
+
+        // The browser's open transaction is untouched
+        assert_eq!(count(&browser), 3);
+        let before = modified(&source).unwrap();
+        browser.execute_batch("COMMIT;").unwrap();
+        // The commit only appends to the WAL, which the consistency check sees
+        assert_ne!(modified(&source).unwrap(), before);
+
+        drop(snapshot);
+        assert!(!snapshot_dir.exists());
+    }
+
+    #[test]
+    fn test_snapshot_rollback_journal() {
+        let source_dir = tempfile::tempdir().unwrap();
+        let source = source_dir.path().join("places.sqlite");
+        Connection::open(&source)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE urls (id INTEGER PRIMARY KEY, url TEXT);
+                 INSERT INTO urls (url) VALUES ('https://solana.com/');",
+            )
+            .unwrap();
+
+        let snapshot = Snapshot::create(&source).unwrap();
+        assert!(!with_suffix(snapshot.path(), "-wal").exists());
+        assert_eq!(count(&snapshot.open().unwrap()), 1);
+    }
+
+    #[test]
+    fn test_snapshot_missing_database() {
+        let source_dir = tempfile::tempdir().unwrap();
+        assert!(Snapshot::create(&source_dir.path().join("History")).is_err());
+    }
+}
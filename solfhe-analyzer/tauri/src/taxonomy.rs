@@ -0,0 +1,232 @@
+// Interest taxonomy loaded from data/taxonomy.json. Each category has a path
+// (`Crypto > DeFi > Lending`), synonyms matched against normalised keywords and
+// domain patterns matched against the visited host. A category's score also
+// counts towards every ancestor, so `Crypto` sums all crypto interests.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::error::Error;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::normalize;
+
+static TAXONOMY: &str = include_str!("../data/taxonomy.json");
+
+/// The data file version this build understands.
+pub const TAXONOMY_VERSION: u32 = 1;
+/// A visit to one of a category's domains counts like this many keyword hits.
+pub const DOMAIN_MATCH_WEIGHT: u32 = 3;
+
+pub const PATH_SEPARATOR: &str = " > ";
+
+#[derive(Debug, Deserialize)]
+struct TaxonomyFile {
+    version: u32,
+    categories: Vec<CategoryEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CategoryEntry {
+    path: Vec<String>,
+    #[serde(default)]
+    synonyms: Vec<String>,
+    /// Registrable domains or hosts; subdomains match too.
+    #[serde(default)]
+    domains: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CategoryScore {
+    pub category: String,
+    pub score: u32,
+}
+
+#[derive(Debug)]
+pub struct Taxonomy {
+    /// Full paths joined with `PATH_SEPARATOR`, in file order.
+    categories: Vec<String>,
+    /// Stemmed synonym -> category index.
+    synonyms: HashMap<String, usize>,
+    domains: Vec<(String, usize)>,
+}
+
+pub fn embedded() -> &'static Taxonomy {
+    static EMBEDDED: OnceLock<Taxonomy> = OnceLock::new();
+    EMBEDDED.get_or_init(|| Taxonomy::parse(TAXONOMY).expect("embedded taxonomy is valid"))
+}
+
+impl Taxonomy {
+    pub fn parse(contents: &str) -> Result<Self, Box<dyn Error>> {
+        let file: TaxonomyFile = serde_json::from_str(contents)?;
+        if file.version != TAXONOMY_VERSION {
+            return Err(format!(
+                "taxonomy version {} is not supported (expected {})",
+                file.version, TAXONOMY_VERSION
+            )
+            .into());
+        }
+
+        let mut taxonomy = Self {
+            categories: Vec::new(),
+            synonyms: HashMap::new(),
+            domains: Vec::new(),
+        };
+        let mut seen_paths = HashSet::new();
+        for (index, entry) in file.categories.into_iter().enumerate() {
+            if entry.path.is_empty() || entry.path.iter().any(|part| part.trim().is_empty()) {
+                return Err(format!("category {} has an empty path", index).into());
+            }
+            let path = entry.path.join(PATH_SEPARATOR);
+            if !seen_paths.insert(path.clone()) {
+                return Err(format!("category {} is listed twice", path).into());
+            }
+
+            for synonym in &entry.synonyms {
+                if synonym.is_empty() || synonym.chars().any(|c| !c.is_alphanumeric() || c.is_uppercase()) {
+                    return Err(format!("{}: synonym {:?} must be one lowercase word", path, synonym).into());
+                }
+                let stem = normalize::stem(synonym);
+                match taxonomy.synonyms.insert(stem, index) {
+                    Some(other) if other != index => {
+                        return Err(format!(
+                            "{}: synonym {:?} is already used by {}",
+                            path, synonym, taxonomy.categories[other]
+                        )
+                        .into());
+                    },
+                    _ => {},
+                }
+            }
+            for domain in entry.domains {
+                if domain.is_empty() || domain.contains('/') || domain.contains(':') || domain != domain.to_lowercase() {
+                    return Err(format!("{}: domain {:?} must be a lowercase host name", path, domain).into());
+                }
+                taxonomy.domains.push((domain, index));
+            }
+            taxonomy.categories.push(path);
+        }
+        Ok(taxonomy)
+    }
+
+    /// Whether a lowercase keyword is one of the taxonomy's synonyms.
+    pub fn is_synonym(&self, word: &str) -> bool {
+        self.synonyms.contains_key(&normalize::stem(word))
+    }
+
+    /// Whether `path` is a category or the ancestor of one.
+    pub fn contains(&self, path: &str) -> bool {
+        self.categories.iter().any(|category| {
+            category == path || category.strip_prefix(path).is_some_and(|rest| rest.starts_with(PATH_SEPARATOR))
+        })
+    }
+
+    fn category_for_keyword(&self, stem: &str) -> Option<usize> {
+        self.synonyms.get(stem).copied()
+    }
+
+    fn category_for_host(&self, host: &str) -> Option<usize> {
+        let host = host.trim_end_matches('.').to_lowercase();
+        self.domains
+            .iter()
+            .find(|(domain, _)| {
+                host == *domain || host.strip_suffix(domain.as_str()).is_some_and(|rest| rest.ends_with('.'))
+            })
+            .map(|&(_, index)| index)
+    }
+
+    /// Category scores for one page from its host and its normalised keyword
+    /// counts, highest first; ties are ordered by category path.
+    pub fn score(&self, host: Option<&str>, keyword_counts: &HashMap<String, u32>) -> Vec<CategoryScore> {
+        let mut scores: BTreeMap<&str, u32> = BTreeMap::new();
+        let mut add = |index: usize, score: u32| {
+            // Every prefix of the path: `Crypto`, `Crypto > DeFi`, `Crypto > DeFi > Lending`
+            let path = &self.categories[index];
+            let ancestors = path.match_indices(PATH_SEPARATOR).map(|(end, _)| &path[..end]);
+            for category in ancestors.chain(std::iter::once(path.as_str())) {
+                *scores.entry(category).or_insert(0) += score;
+            }
+        };
+
+        if let Some(index) = host.and_then(|host| self.category_for_host(host)) {
+            add(index, DOMAIN_MATCH_WEIGHT);
+        }
+        for (stem, &count) in keyword_counts {
+            if let Some(index) = self.category_for_keyword(stem) {
+                add(index, count);
+            }
+        }
+
+        let mut scores: Vec<CategoryScore> = scores
+            .into_iter()
+            .map(|(category, score)| CategoryScore { category: category.to_string(), score })
+            .collect();
+        // Stable sort keeps the BTreeMap's path order among equal scores
+        scores.sort_by_key(|s| std::cmp::Reverse(s.score));
+        scores
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(words: &[(&str, u32)]) -> HashMap<String, u32> {
+        words.iter().map(|&(word, count)| (normalize::stem(word), count)).collect()
+    }
+
+    fn score(category: &str, score: u32) -> CategoryScore {
+        CategoryScore { category: category.to_string(), score }
+    }
+
+    #[test]
+    fn test_embedded_taxonomy() {
+        let taxonomy = embedded();
+        assert!(taxonomy.is_synonym("aave"));
+        assert!(taxonomy.is_synonym("staking"));
+        assert!(taxonomy.is_synonym("nfts"));
+        assert!(!taxonomy.is_synonym("popup"));
+        assert!(taxonomy.contains("Crypto > DeFi > Lending"));
+        assert!(taxonomy.contains("Crypto > DeFi"));
+        assert!(!taxonomy.contains("Crypto > De"));
+        assert!(!taxonomy.contains("Cooking"));
+    }
+
+    #[test]
+    fn test_score_rolls_up_to_ancestors() {
+        let taxonomy = embedded();
+        let scores = taxonomy.score(Some("app.aave.com"), &counts(&[("lending", 2), ("solana", 1), ("popup", 5)]));
+        assert_eq!(
+            scores,
+            vec![
+                score("Crypto", 6),
+                score("Crypto > DeFi", 5),
+                score("Crypto > DeFi > Lending", 5),
+                score("Crypto > L1", 1),
+                score("Crypto > L1 > Solana", 1),
+            ]
+        );
+
+        // Only whole labels match a domain pattern
+        assert!(taxonomy.score(Some("notaave.com"), &HashMap::new()).is_empty());
+        assert_eq!(
+            taxonomy.score(Some("gemini.google.com"), &HashMap::new()),
+            vec![score("Technology", 3), score("Technology > AI", 3)]
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_files() {
+        let parse = |json: &str| Taxonomy::parse(json).unwrap_err().to_string();
+        assert!(parse(r#"{"version": 2, "categories": []}"#).contains("version 2"));
+        assert!(parse(r#"{"version": 1, "categories": [{"path": []}]}"#).contains("empty path"));
+        assert!(parse(
+            r#"{"version": 1, "categories": [{"path": ["A"], "synonyms": ["token"]}, {"path": ["B"], "synonyms": ["tokens"]}]}"#
+        )
+        .contains("already used by A"));
+        assert!(parse(r#"{"version": 1, "categories": [{"path": ["A"], "synonyms": ["two words"]}]}"#)
+            .contains("one lowercase word"));
+        assert!(parse(r#"{"version": 1, "categories": [{"path": ["A"], "domains": ["https://a.com"]}]}"#)
+            .contains("lowercase host name"));
+    }
+}